@@ -0,0 +1,62 @@
+use std::str::FromStr;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use pngme::chunk::Chunk;
+use pngme::chunk_type::ChunkType;
+use pngme::png::Png;
+
+/// Builds a PNG with `count` chunks of `size` bytes each. The data is a
+/// fixed byte pattern so runs are comparable.
+fn synthetic_png(count: usize, size: usize) -> Vec<u8> {
+    let chunk_type = ChunkType::from_str("ruSt").unwrap();
+    let data: Vec<u8> = (0..size).map(|i| (i % 251) as u8).collect();
+    let chunks = (0..count)
+        .map(|_| Chunk::new(chunk_type.clone(), data.clone()))
+        .collect();
+
+    Png::from_chunks(chunks).as_bytes()
+}
+
+fn cases() -> [(&'static str, Vec<u8>); 2] {
+    [
+        ("many_small", synthetic_png(10_000, 16)),
+        ("few_large", synthetic_png(4, 1 << 20)),
+    ]
+}
+
+fn bench_try_from(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Png::try_from");
+    for (name, bytes) in cases() {
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &bytes, |b, bytes| {
+            b.iter(|| Png::try_from(black_box(bytes.as_slice())).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_from_bytes_unchecked(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Png::from_bytes_unchecked");
+    for (name, bytes) in cases() {
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &bytes, |b, bytes| {
+            b.iter(|| Png::from_bytes_unchecked(black_box(bytes.as_slice())).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_as_bytes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Png::as_bytes");
+    for (name, bytes) in cases() {
+        let png = Png::try_from(bytes.as_slice()).unwrap();
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &png, |b, png| {
+            b.iter(|| black_box(png).as_bytes())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_try_from, bench_from_bytes_unchecked, bench_as_bytes);
+criterion_main!(benches);
@@ -0,0 +1,131 @@
+use std::path::PathBuf;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+#[derive(Debug, Parser)]
+#[command(name = "pngme", version, about = "Hide secret messages in PNG files")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Hide a message in a chunk of the given type
+    Encode(EncodeArgs),
+    /// Print the message stored in a chunk of the given type
+    Decode(DecodeArgs),
+    /// Remove the first chunk of the given type
+    Remove(RemoveArgs),
+    /// List every chunk in the file
+    Print(PrintArgs),
+    /// Write the raw data of the first chunk of the given type to a file
+    Extract(ExtractArgs),
+    /// Count the chunks of each type
+    List(ListArgs),
+    /// Check every CRC and the overall chunk layout
+    Verify(VerifyArgs),
+    /// Show which chunks were added, removed, or changed between two files
+    Diff(DiffArgs),
+    /// Remove private and unsafe-to-copy ancillary chunks
+    Sanitize(SanitizeArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct EncodeArgs {
+    pub file_path: PathBuf,
+    /// Chunk type, or `-` to read it from stdin
+    pub chunk_type: String,
+    /// Message, or `-` to read it from stdin
+    #[arg(required_unless_present = "file")]
+    pub message: Option<String>,
+    /// Store the contents of this file instead of a message
+    #[arg(long, conflicts_with = "message")]
+    pub file: Option<PathBuf>,
+    /// Write the result here instead of overwriting the input file
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+    /// Deflate the message with zlib before storing it
+    #[arg(long)]
+    pub compress: bool,
+    /// Print the resulting chunks and file size without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+    /// Insert the chunk at this index instead of before IEND
+    #[arg(long)]
+    pub at: Option<usize>,
+    /// Allow a critical chunk type, which most decoders will reject
+    #[arg(long)]
+    pub force: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct DecodeArgs {
+    pub file_path: PathBuf,
+    pub chunk_type: String,
+    /// Write the chunk's data to this file instead of printing it
+    #[arg(long)]
+    pub out: Option<PathBuf>,
+    /// Output the data exactly as stored, without inflating data written by
+    /// `encode --compress`
+    #[arg(long)]
+    pub raw: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct RemoveArgs {
+    pub file_path: PathBuf,
+    pub chunk_type: String,
+}
+
+#[derive(Debug, Args)]
+pub struct ExtractArgs {
+    pub file_path: PathBuf,
+    pub chunk_type: String,
+    pub output: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct ListArgs {
+    pub file_path: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct VerifyArgs {
+    pub file_path: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct SanitizeArgs {
+    pub file_path: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct DiffArgs {
+    pub old_path: PathBuf,
+    pub new_path: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct PrintArgs {
+    pub file_path: PathBuf,
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+    /// Only list chunks of this type
+    #[arg(long = "type")]
+    pub chunk_type: Option<String>,
+    /// Show a hex dump of each chunk's data (text format only)
+    #[arg(long)]
+    pub data: bool,
+    /// Dump at most this many bytes of each chunk
+    #[arg(long, default_value_t = 256)]
+    pub max_bytes: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// One line per chunk
+    Text,
+    /// A JSON array with the type, length, CRC, and file offset of each chunk
+    Json,
+}
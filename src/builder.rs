@@ -0,0 +1,115 @@
+use alloc::vec::Vec;
+
+use crate::chunk::Chunk;
+use crate::error::Result;
+use crate::ihdr::IhdrData;
+use crate::png::{Png, IDAT, IEND};
+
+/// Assembles a [`Png`] chunk by chunk. `IHDR` always goes first and `IEND`
+/// is appended by [`PngBuilder::build`].
+#[derive(Debug, Clone, Default)]
+pub struct PngBuilder {
+    ihdr: Option<IhdrData>,
+    chunks: Vec<Chunk>,
+}
+
+impl PngBuilder {
+    pub fn new() -> PngBuilder {
+        PngBuilder::default()
+    }
+
+    pub fn with_ihdr(mut self, ihdr: IhdrData) -> PngBuilder {
+        self.ihdr = Some(ihdr);
+        self
+    }
+
+    pub fn add_chunk(mut self, chunk: Chunk) -> PngBuilder {
+        self.chunks.push(chunk);
+        self
+    }
+
+    pub fn add_idat(self, data: Vec<u8>) -> PngBuilder {
+        self.add_chunk(Chunk::new(IDAT, data))
+    }
+
+    pub fn build(self) -> Result<Png> {
+        let mut chunks = Vec::with_capacity(self.chunks.len() + 2);
+        chunks.extend(self.ihdr.map(Chunk::from));
+        chunks.extend(self.chunks);
+        chunks.push(Chunk::new(IEND, Vec::new()));
+
+        let png = Png::from_chunks(chunks);
+        png.validate_structure()?;
+        Ok(png)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::PngError;
+    use flate2::write::ZlibEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    fn one_pixel_ihdr() -> IhdrData {
+        IhdrData {
+            width: 1,
+            height: 1,
+            bit_depth: 8,
+            color_type: 2,
+            compression: 0,
+            filter: 0,
+            interlace: 0,
+        }
+    }
+
+    fn one_pixel_idat() -> Vec<u8> {
+        // One scanline: filter type 0 followed by a single RGB pixel.
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&[0, 255, 0, 0]).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_build_minimal_png() {
+        let png = PngBuilder::new()
+            .with_ihdr(one_pixel_ihdr())
+            .add_idat(one_pixel_idat())
+            .build()
+            .unwrap();
+
+        let reparsed = Png::try_from(png.as_bytes().as_ref()).unwrap();
+        assert_eq!(reparsed, png);
+        assert_eq!(reparsed.dimensions(), Some((1, 1)));
+        assert_eq!(reparsed.chunks().len(), 3);
+        assert_eq!(reparsed.chunks()[2].chunk_type().to_string(), "IEND");
+    }
+
+    #[test]
+    fn test_build_keeps_chunk_order() {
+        let png = PngBuilder::new()
+            .with_ihdr(one_pixel_ihdr())
+            .add_chunk(Chunk::text("Comment", "hello").unwrap())
+            .add_idat(one_pixel_idat())
+            .build()
+            .unwrap();
+
+        let types: Vec<String> = png.iter().map(|chunk| chunk.chunk_type().to_string()).collect();
+        assert_eq!(types, ["IHDR", "tEXt", "IDAT", "IEND"]);
+    }
+
+    #[test]
+    fn test_build_without_ihdr() {
+        let result = PngBuilder::new().add_idat(one_pixel_idat()).build();
+
+        assert!(matches!(result, Err(PngError::Structure(_))));
+    }
+
+    #[test]
+    fn test_build_without_idat() {
+        let result = PngBuilder::new().with_ihdr(one_pixel_ihdr()).build();
+
+        assert!(matches!(result, Err(PngError::Structure(_))));
+    }
+}
@@ -1,7 +1,21 @@
-use std::fmt;
-use std::io::{BufReader, Read};
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io::{Read, Write};
+
+#[cfg(feature = "std")]
+use flate2::read::ZlibDecoder;
+#[cfg(feature = "std")]
+use flate2::write::ZlibEncoder;
+#[cfg(feature = "std")]
+use flate2::Compression;
+
+use crate::chunk_ref::ChunkRef;
 use crate::chunk_type::ChunkType;
-struct Chunk{
+use crate::error::{PngError, Result};
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk{
     length:u32,
     chunk_type:ChunkType,
     data:Vec<u8>,
@@ -9,86 +23,193 @@ struct Chunk{
 }
 
 impl Chunk {
-    fn new(chunk_type: ChunkType, data: Vec<u8>) -> Chunk{
-        let crc=Self::calculate_crc(&chunk_type, &data);
-        Self{
-            length:data.len() as u32,
+    pub const MAX_LENGTH: u32 = 0x7FFF_FFFF;
+
+    /// Prefix of data written by [`Chunk::new_compressed`]. Like the null
+    /// separator and method byte in `zTXt`, it says explicitly that a zlib
+    /// stream follows, so nothing has to be guessed from the data itself.
+    pub const COMPRESSED_MARKER: &'static [u8] = b"\0zlib\0";
+
+    /// # Panics
+    ///
+    /// Panics if `data` is longer than [`Chunk::MAX_LENGTH`]; use
+    /// [`Chunk::try_new`] when the size isn't known to be in range.
+    pub fn new(chunk_type: ChunkType, data: Vec<u8>) -> Chunk{
+        match Self::try_new(chunk_type, data) {
+            Ok(chunk) => chunk,
+            Err(e) => panic!("{e}"),
+        }
+    }
+
+    pub fn try_new(chunk_type: ChunkType, data: Vec<u8>) -> Result<Chunk> {
+        let length = checked_length(data.len())?;
+        let crc = Self::calculate_crc(&chunk_type, &data);
+        Ok(Self {
+            length,
             chunk_type,
             data,
-            crc
-        }
+            crc,
+        })
     }
-    fn length(&self) -> u32{
-        self.length as u32
+
+    /// Builds a chunk with a CRC that is already known, e.g. when copying a
+    /// chunk verbatim. The CRC is stored as-is; use `is_crc_valid` to check it.
+    pub fn from_parts(chunk_type: ChunkType, data: Vec<u8>, crc: u32) -> Result<Chunk> {
+        Ok(Self {
+            length: checked_length(data.len())?,
+            chunk_type,
+            data,
+            crc,
+        })
     }
-    fn chunk_type(&self) -> &ChunkType{
+    #[cfg(feature = "std")]
+    /// Like `try_new`, but deflates `data` with zlib and stores it after
+    /// [`Chunk::COMPRESSED_MARKER`].
+    pub fn new_compressed(chunk_type: ChunkType, data: &[u8]) -> Result<Chunk> {
+        let mut encoder = ZlibEncoder::new(Self::COMPRESSED_MARKER.to_vec(), Compression::default());
+        encoder.write_all(data)?;
+        Self::try_new(chunk_type, encoder.finish()?)
+    }
+
+    pub fn length(&self) -> u32{
+        self.length
+    }
+    pub fn chunk_type(&self) -> &ChunkType{
         &self.chunk_type
     }
 
-    fn data(&self) -> &[u8]{
+    pub fn data(&self) -> &[u8]{
         &self.data
     }   
 
-    fn crc(&self) -> u32{
+    pub fn crc(&self) -> u32{
         self.crc
     }
-    fn data_as_string(&self) -> anyhow::Result<String>{
+
+    /// The length as written on the wire, big-endian. Always derived from
+    /// the data itself, so the header can't disagree with the payload. Every
+    /// constructor and `set_data` keep the data within
+    /// [`Chunk::MAX_LENGTH`], so this never truncates.
+    pub fn length_bytes(&self) -> [u8; 4] {
+        debug_assert_eq!(self.length as usize, self.data.len(), "stale chunk length");
+        u32::try_from(self.data.len())
+            .expect("chunk data is kept within Chunk::MAX_LENGTH")
+            .to_be_bytes()
+    }
+
+    /// The CRC as written on the wire, big-endian.
+    pub fn crc_bytes(&self) -> [u8; 4] {
+        self.crc.to_be_bytes()
+    }
+    pub fn data_as_string(&self) -> Result<String>{
         Ok(String::from_utf8(self.data.clone())?)
     }
 
-    fn as_bytes(&self) -> Vec<u8>{
-        self.length
-            .to_be_bytes()
+    /// Decodes the data as Latin-1, the encoding `tEXt` uses. Every byte maps
+    /// to the code point of the same value, so this cannot fail.
+    pub fn data_as_latin1(&self) -> String {
+        decode_latin1(&self.data)
+    }
+
+    /// Whether the data starts with [`Chunk::COMPRESSED_MARKER`].
+    pub fn is_compressed(&self) -> bool {
+        self.data.starts_with(Self::COMPRESSED_MARKER)
+    }
+
+    /// Inflates data written by [`Chunk::new_compressed`]. Data without the
+    /// marker is rejected rather than inflated on a guess.
+    #[cfg(feature = "std")]
+    pub fn data_decompressed(&self) -> Result<Vec<u8>> {
+        let Some(compressed) = self.data.strip_prefix(Self::COMPRESSED_MARKER) else {
+            return Err(PngError::MalformedChunk {
+                chunk_type: self.chunk_type.clone(),
+                reason: "data is not marked as compressed",
+            });
+        };
+
+        let mut data = Vec::new();
+        ZlibDecoder::new(compressed)
+            .read_to_end(&mut data)
+            .map_err(PngError::Decompress)?;
+        Ok(data)
+    }
+
+    #[cfg(feature = "std")]
+    pub fn data_as_string_decompressed(&self) -> Result<String> {
+        Ok(String::from_utf8(self.data_decompressed()?)?)
+    }
+
+    pub fn as_bytes(&self) -> Vec<u8>{
+        self.length_bytes()
             .iter()
             .copied()
             .chain(self.chunk_type().bytes().iter().cloned())
             .chain(self.data.iter().cloned())
-            .chain(self.crc.to_be_bytes().iter().cloned())
+            .chain(self.crc_bytes().iter().cloned())
             .collect()
     }
 
-    pub fn calculate_crc(chunk_type: &ChunkType, data: &[u8]) -> u32 {
-        let crc_data: Vec<u8> = chunk_type
-            .bytes()
-            .iter()
-            .copied()
-            .chain(data.iter().copied())
-            .collect();
-
-        crc::crc32::checksum_ieee(&crc_data)
+    /// Replaces the data, keeping `length` and `crc` in sync with it. Data
+    /// over [`Chunk::MAX_LENGTH`] is rejected and the chunk left unchanged.
+    pub fn set_data(&mut self, data: Vec<u8>) -> Result<()> {
+        self.length = checked_length(data.len())?;
+        self.data = data;
+        self.recompute_crc();
+        Ok(())
     }
-}
 
-impl TryFrom<&[u8]> for Chunk {
-    type Error = anyhow::Error;
+    /// Refreshes `crc` from the current data.
+    pub fn recompute_crc(&mut self) {
+        self.crc = Self::calculate_crc(&self.chunk_type, &self.data);
+    }
 
-    fn try_from(bytes: &[u8]) -> anyhow::Result<Self> {
-        if bytes.len() < 8 {
-            anyhow::bail!("Invalid chunk")
-        }
+    /// Whether the stored CRC matches the chunk type and data.
+    pub fn is_crc_valid(&self) -> bool {
+        self.crc == Self::calculate_crc(&self.chunk_type, &self.data)
+    }
 
-        let mut reader = BufReader::new(bytes);
-        let mut buffer: [u8; 4] = [0, 0, 0, 0];
+    pub fn calculate_crc(chunk_type: &ChunkType, data: &[u8]) -> u32 {
+        let crc = crc32_update(0xFFFF_FFFF, &chunk_type.bytes());
+        crc32_update(crc, data) ^ 0xFFFF_FFFF
+    }
 
-        reader.read_exact(&mut buffer)?;
-        let data_length = u32::from_be_bytes(buffer);
+    #[cfg(feature = "std")]
+    /// Reads the next chunk from `reader`, or `None` if the stream ends
+    /// cleanly before a new chunk starts.
+    pub(crate) fn read_from<R: Read>(reader: &mut R) -> Result<Option<Chunk>> {
+        let Some(data_length) = read_chunk_length(reader)? else {
+            return Ok(None);
+        };
 
+        let mut buffer: [u8; 4] = [0, 0, 0, 0];
         reader.read_exact(&mut buffer)?;
         let chunk_type = ChunkType::try_from(buffer)?;
 
-        let mut data: Vec<u8> = vec![0; data_length as usize];
-        reader.read_exact(&mut data)?;
+        let data = read_chunk_data(reader, data_length)?;
 
         reader.read_exact(&mut buffer)?;
         let crc = u32::from_be_bytes(buffer);
 
+        Self::verified(chunk_type, data, crc).map(Some)
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn write_to<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        writer.write_all(&self.length_bytes())?;
+        writer.write_all(&self.chunk_type.bytes())?;
+        writer.write_all(&self.data)?;
+        writer.write_all(&self.crc_bytes())
+    }
+
+    #[cfg(feature = "std")]
+    pub(crate) fn verified(chunk_type: ChunkType, data: Vec<u8>, crc: u32) -> Result<Chunk> {
         let computed_crc = Chunk::calculate_crc(&chunk_type, &data);
         if crc != computed_crc {
-            anyhow::bail!("CRC check failed");
+            return Err(PngError::CrcMismatch { expected: computed_crc, actual: crc });
         }
 
         Ok(Self {
-            length: data_length,
+            length: checked_length(data.len())?,
             chunk_type,
             data,
             crc,
@@ -96,15 +217,117 @@ impl TryFrom<&[u8]> for Chunk {
     }
 }
 
+fn checked_length(len: usize) -> Result<u32> {
+    u32::try_from(len)
+        .ok()
+        .filter(|&length| length <= Chunk::MAX_LENGTH)
+        .ok_or(PngError::PayloadTooLarge(len))
+}
+
+/// Reads a chunk's length field, or `None` if the stream ends cleanly
+/// before it. Lengths over [`Chunk::MAX_LENGTH`] are rejected.
+#[cfg(feature = "std")]
+pub(crate) fn read_chunk_length<R: Read>(reader: &mut R) -> Result<Option<u32>> {
+    let mut buffer = [0; 4];
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match reader.read(&mut buffer[filled..]) {
+            Ok(0) if filled == 0 => return Ok(None),
+            Ok(0) => return Err(PngError::BadLength { expected: 4, found: filled }),
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    let length = u32::from_be_bytes(buffer);
+    if length > Chunk::MAX_LENGTH {
+        return Err(PngError::LengthTooLarge(length));
+    }
+    Ok(Some(length))
+}
+
+/// Reads `length` bytes of chunk data. Goes through `take` so a bogus length
+/// can't force a huge allocation up front.
+#[cfg(feature = "std")]
+pub(crate) fn read_chunk_data<R: Read>(reader: &mut R, length: u32) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    reader.take(u64::from(length)).read_to_end(&mut data)?;
+    if data.len() != length as usize {
+        return Err(PngError::BadLength {
+            expected: length as usize,
+            found: data.len(),
+        });
+    }
+    Ok(data)
+}
+
+pub(crate) fn decode_latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| char::from(b)).collect()
+}
+
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 { 0xEDB8_8320 ^ (c >> 1) } else { c >> 1 };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+};
+
+/// The CRC-32 used by PNG (ISO 3309 / ITU-T V.42, polynomial 0xEDB88320).
+/// A chunk's CRC is this over its type bytes followed by its data.
+pub fn png_crc32(bytes: &[u8]) -> u32 {
+    crc32_update(0xFFFF_FFFF, bytes) ^ 0xFFFF_FFFF
+}
+
+/// Feeds `bytes` into a running CRC, before the final inversion, so a CRC
+/// can be computed over several slices without joining them.
+fn crc32_update(crc: u32, bytes: &[u8]) -> u32 {
+    bytes.iter().fold(crc, |crc, &byte| {
+        CRC_TABLE[((crc ^ byte as u32) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+impl TryFrom<&[u8]> for Chunk {
+    type Error = PngError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        ChunkRef::try_from(bytes).map(Chunk::from)
+    }
+}
+
+impl From<ChunkRef<'_>> for Chunk {
+    fn from(chunk: ChunkRef<'_>) -> Self {
+        Self {
+            length: chunk.length(),
+            chunk_type: chunk.chunk_type().clone(),
+            data: chunk.data().to_vec(),
+            crc: chunk.crc(),
+        }
+    }
+}
+
 impl fmt::Display for Chunk {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "Chunk {{",)?;
-        writeln!(f, "  Length: {}", self.length)?;
-        writeln!(f, "  Type: {}", self.chunk_type)?;
-        writeln!(f, "  Data: {} bytes", self.data.len())?;
-        writeln!(f, "  Crc: {}", self.crc)?;
-        writeln!(f, "}}",)?;
-        Ok(())
+        write!(
+            f,
+            "Chunk {{ type: {}, length: {}, crc: {:#010X}",
+            self.chunk_type, self.length, self.crc
+        )?;
+        // Only show data that is short enough to read at a glance.
+        let printable = |b: &u8| b.is_ascii_graphic() || *b == b' ';
+        if !self.data.is_empty() && self.data.len() <= 32 && self.data.iter().all(printable) {
+            write!(f, ", data: {:?}", String::from_utf8_lossy(&self.data))?;
+        }
+        write!(f, " }}")
     }
 }
 
@@ -142,6 +365,57 @@ mod tests {
         assert_eq!(chunk.crc(), 2882656334);
     }
 
+    #[test]
+    fn test_new_empty_chunk() {
+        let chunk_type = ChunkType::from_str("IEND").unwrap();
+        let chunk = Chunk::new(chunk_type, Vec::new());
+        assert_eq!(chunk.length(), 0);
+        assert!(chunk.data().is_empty());
+        assert_eq!(chunk.crc(), 0xAE426082);
+    }
+
+    #[test]
+    fn test_empty_chunk_round_trip() {
+        let bytes = [0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xAE, 0x42, 0x60, 0x82];
+
+        let chunk = Chunk::try_from(bytes.as_ref()).unwrap();
+        assert_eq!(chunk.chunk_type().to_string(), "IEND");
+        assert!(chunk.data().is_empty());
+        assert_eq!(chunk.as_bytes(), bytes);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_read_empty_chunk() {
+        let chunk = Chunk::new(ChunkType::from_str("IEND").unwrap(), Vec::new());
+        let bytes = chunk.as_bytes();
+
+        let mut reader = bytes.as_slice();
+        assert_eq!(Chunk::read_from(&mut reader).unwrap(), Some(chunk));
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_png_crc32_check_value() {
+        assert_eq!(png_crc32(b"123456789"), 0xCBF43926);
+        assert_eq!(png_crc32(b""), 0);
+    }
+
+    #[test]
+    fn test_png_crc32_matches_chunk_crc() {
+        let chunk = testing_chunk();
+        let crc_data: Vec<u8> = chunk
+            .chunk_type()
+            .bytes()
+            .iter()
+            .chain(chunk.data())
+            .copied()
+            .collect();
+
+        assert_eq!(png_crc32(&crc_data), 2882656334);
+        assert_eq!(png_crc32(b"IEND"), 0xAE426082);
+    }
+
     #[test]
     fn test_chunk_length() {
         let chunk = testing_chunk();
@@ -162,6 +436,80 @@ mod tests {
         assert_eq!(chunk_string, expected_chunk_string);
     }
 
+    #[test]
+    fn test_secret_data_as_string() {
+        let chunk_type = ChunkType::from_str("ruSt").unwrap();
+        let chunk = Chunk::new(chunk_type, "This is a secret".as_bytes().to_vec());
+        assert_eq!(chunk.data_as_string().unwrap(), "This is a secret");
+    }
+
+    #[test]
+    fn test_invalid_utf8_data_as_string() {
+        let chunk_type = ChunkType::from_str("ruSt").unwrap();
+        let chunk = Chunk::new(chunk_type, vec![0xFF, 0xFE]);
+        assert!(chunk.data_as_string().is_err());
+    }
+
+    #[test]
+    fn test_data_as_latin1() {
+        let chunk = Chunk::new(ChunkType::from_str("tEXt").unwrap(), vec![0xE9]);
+        assert_eq!(chunk.data_as_latin1(), "é");
+        assert!(chunk.data_as_string().is_err());
+
+        let all_bytes: Vec<u8> = (0..=255).collect();
+        let chunk = Chunk::new(ChunkType::from_str("tEXt").unwrap(), all_bytes);
+        assert_eq!(chunk.data_as_latin1().chars().count(), 256);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_compressed_round_trip() {
+        let message = "This is where your secret message will be! ".repeat(240);
+        assert!(message.len() > 10_000);
+
+        let chunk_type = ChunkType::from_str("ruSt").unwrap();
+        let chunk = Chunk::new_compressed(chunk_type, message.as_bytes()).unwrap();
+
+        assert!((chunk.length() as usize) < message.len());
+        assert!(chunk.is_compressed());
+        assert!(chunk.data().starts_with(Chunk::COMPRESSED_MARKER));
+        assert_eq!(chunk.data_as_string_decompressed().unwrap(), message);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_decompress_plain_data() {
+        let chunk = testing_chunk();
+
+        assert!(!chunk.is_compressed());
+        assert!(matches!(
+            chunk.data_as_string_decompressed(),
+            Err(PngError::MalformedChunk { .. })
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_unmarked_zlib_is_not_compressed() {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(b"a valid zlib stream").unwrap();
+        let chunk = Chunk::new(ChunkType::from_str("ruSt").unwrap(), encoder.finish().unwrap());
+
+        assert!(!chunk.is_compressed());
+        assert!(chunk.data_decompressed().is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_marked_data_that_does_not_inflate() {
+        let mut data = Chunk::COMPRESSED_MARKER.to_vec();
+        data.extend_from_slice(b"not zlib");
+        let chunk = Chunk::new(ChunkType::from_str("ruSt").unwrap(), data);
+
+        assert!(chunk.is_compressed());
+        assert!(matches!(chunk.data_decompressed(), Err(PngError::Decompress(_))));
+    }
+
     #[test]
     fn test_chunk_crc() {
         let chunk = testing_chunk();
@@ -213,7 +561,55 @@ mod tests {
 
         let chunk = Chunk::try_from(chunk_data.as_ref());
 
-        assert!(chunk.is_err());
+        assert!(matches!(
+            chunk,
+            Err(PngError::CrcMismatch { expected: 2882656334, actual: 2882656333 })
+        ));
+    }
+
+    #[test]
+    fn test_truncated_chunk_from_bytes() {
+        let chunk_bytes = testing_chunk().as_bytes();
+
+        for len in [0, 4, 8, 11, 12, chunk_bytes.len() - 1] {
+            let chunk = Chunk::try_from(&chunk_bytes[..len]);
+            assert!(chunk.is_err(), "accepted a {len}-byte buffer");
+        }
+    }
+
+    #[test]
+    fn test_chunk_as_bytes_round_trip() {
+        let mut seed: u32 = 0x1234_5678;
+        let data: Vec<u8> = (0..600)
+            .map(|_| {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                (seed >> 24) as u8
+            })
+            .collect();
+        let chunk = Chunk::new(ChunkType::from_str("RuSt").unwrap(), data);
+
+        let bytes = chunk.as_bytes();
+        assert_eq!(bytes.len(), 12 + 600);
+        assert_eq!(&bytes[..4], &600u32.to_be_bytes());
+        assert_eq!(&bytes[4..8], b"RuSt");
+        assert_eq!(&bytes[bytes.len() - 4..], &chunk.crc().to_be_bytes());
+
+        let parsed = Chunk::try_from(bytes.as_slice()).unwrap();
+        assert_eq!(parsed, chunk);
+    }
+
+    #[test]
+    fn test_oversized_length_from_bytes() {
+        let chunk_data: Vec<u8> = 0xFFFF_FFFFu32
+            .to_be_bytes()
+            .iter()
+            .chain(b"RuSt")
+            .chain(&[0; 8])
+            .copied()
+            .collect();
+
+        let chunk = Chunk::try_from(chunk_data.as_ref());
+        assert!(matches!(chunk, Err(PngError::LengthTooLarge(0xFFFF_FFFF))));
     }
 
     #[test]
@@ -236,5 +632,129 @@ mod tests {
         
         let _chunk_string = format!("{}", chunk);
     }
-}
 
+    #[test]
+    fn test_chunk_display() {
+        let chunk = testing_chunk();
+        assert_eq!(
+            chunk.to_string(),
+            "Chunk { type: RuSt, length: 42, crc: 0xABD1D84E }"
+        );
+
+        let chunk = Chunk::new(ChunkType::from_str("tEXt").unwrap(), b"Author".to_vec());
+        let chunk_string = chunk.to_string();
+        assert!(chunk_string.contains("type: tEXt"));
+        assert!(chunk_string.contains("length: 6"));
+        assert!(chunk_string.contains(r#"data: "Author""#));
+
+        let chunk = Chunk::new(ChunkType::from_str("ruSt").unwrap(), vec![0, 159, 146, 150]);
+        assert!(!chunk.to_string().contains("data"));
+    }
+
+    #[test]
+    fn test_recompute_crc() {
+        let original = testing_chunk();
+        let mut data = original.data().to_vec();
+        data.extend_from_slice(b" Or here.");
+        let mut chunk =
+            Chunk::from_parts(original.chunk_type().clone(), data, original.crc()).unwrap();
+        assert!(Chunk::try_from(chunk.as_bytes().as_ref()).is_err());
+
+        chunk.recompute_crc();
+
+        assert_eq!(chunk.length(), 51);
+        assert_eq!(Chunk::try_from(chunk.as_bytes().as_ref()).unwrap(), chunk);
+    }
+
+    #[test]
+    fn test_set_data() {
+        let mut chunk = testing_chunk();
+        chunk.set_data(b"A new message".to_vec()).unwrap();
+
+        assert_eq!(chunk.length(), 13);
+        assert_eq!(chunk.data_as_string().unwrap(), "A new message");
+        let expected = Chunk::new(ChunkType::from_str("RuSt").unwrap(), b"A new message".to_vec());
+        assert_eq!(chunk, expected);
+        assert_eq!(Chunk::try_from(chunk.as_bytes().as_ref()).unwrap(), chunk);
+    }
+
+    #[test]
+    fn test_length_field_tracks_set_data() {
+        let mut chunk = testing_chunk();
+        chunk.set_data(vec![0xAB; 300]).unwrap();
+
+        let bytes = chunk.as_bytes();
+        assert_eq!(bytes[..4], 300u32.to_be_bytes());
+        assert_eq!(bytes.len(), 12 + 300);
+
+        chunk.set_data(Vec::new()).unwrap();
+        assert_eq!(chunk.as_bytes()[..4], [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_is_crc_valid() {
+        let chunk = testing_chunk();
+        assert!(chunk.is_crc_valid());
+
+        let chunk_type = chunk.chunk_type().clone();
+        let chunk = Chunk::from_parts(chunk_type, chunk.data().to_vec(), 0xDEAD_BEEF).unwrap();
+        assert!(!chunk.is_crc_valid());
+        assert_eq!(chunk.crc(), 0xDEAD_BEEF);
+    }
+
+    #[test]
+    fn test_from_parts() {
+        let chunk = testing_chunk();
+        let chunk_type = chunk.chunk_type().clone();
+        let copy = Chunk::from_parts(chunk_type.clone(), chunk.data().to_vec(), chunk.crc()).unwrap();
+        assert_eq!(copy, chunk);
+        assert_eq!(Chunk::try_from(copy.as_bytes().as_ref()).unwrap(), chunk);
+
+        let arbitrary = Chunk::from_parts(chunk_type, chunk.data().to_vec(), 0x0102_0304).unwrap();
+        let bytes = arbitrary.as_bytes();
+        assert_eq!(bytes[bytes.len() - 4..], [1, 2, 3, 4]);
+        assert!(!arbitrary.is_crc_valid());
+    }
+
+    #[test]
+    fn test_length_and_crc_bytes() {
+        let chunk = Chunk::new(ChunkType::from_str("ruSt").unwrap(), vec![0; 258]);
+        assert_eq!(chunk.length_bytes(), [0, 0, 1, 2]);
+
+        let chunk = testing_chunk();
+        assert_eq!(chunk.crc_bytes(), [0xAB, 0xD1, 0xD8, 0x4E]);
+        let bytes = chunk.as_bytes();
+        assert_eq!(bytes[..4], chunk.length_bytes());
+        assert_eq!(bytes[bytes.len() - 4..], chunk.crc_bytes());
+    }
+
+    #[test]
+    fn test_checked_length_boundary() {
+        let max = Chunk::MAX_LENGTH as usize;
+
+        assert_eq!(checked_length(0).unwrap(), 0);
+        assert_eq!(checked_length(max).unwrap(), Chunk::MAX_LENGTH);
+        assert!(matches!(
+            checked_length(max + 1),
+            Err(PngError::PayloadTooLarge(n)) if n == max + 1
+        ));
+        assert_eq!(
+            checked_length(max + 1).unwrap_err().to_string(),
+            format!("message of {} bytes exceeds PNG chunk limit", max + 1)
+        );
+    }
+
+    #[test]
+    fn test_try_new() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let data = b"This is where your secret message will be!".to_vec();
+
+        assert_eq!(Chunk::try_new(chunk_type, data).unwrap(), testing_chunk());
+    }
+
+    #[test]
+    fn test_chunk_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Chunk>();
+    }
+}
@@ -0,0 +1,93 @@
+use std::io::Read;
+
+use crate::chunk::Chunk;
+use crate::error::Result;
+use crate::signature::PngSignature;
+
+/// Reads chunks from a PNG stream one at a time, so only the current chunk
+/// is held in memory. The signature is checked on the first call to `next`,
+/// and iteration ends after `IEND`, at the end of the stream, or after the
+/// first error.
+#[derive(Debug)]
+pub struct ChunkReader<R> {
+    reader: R,
+    started: bool,
+    done: bool,
+}
+
+impl<R: Read> ChunkReader<R> {
+    pub fn new(reader: R) -> ChunkReader<R> {
+        Self {
+            reader,
+            started: false,
+            done: false,
+        }
+    }
+
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    fn read_chunk(&mut self) -> Result<Option<Chunk>> {
+        if !self.started {
+            self.started = true;
+            PngSignature::read_from(&mut self.reader)?;
+        }
+        Chunk::read_from(&mut self.reader)
+    }
+}
+
+impl<R: Read> Iterator for ChunkReader<R> {
+    type Item = Result<Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let result = self.read_chunk().transpose();
+        self.done = match &result {
+            Some(Ok(chunk)) => chunk.chunk_type() == "IEND",
+            _ => true,
+        };
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::PngError;
+    use crate::png::Png;
+    use std::io::Cursor;
+
+    const SPLIT_IDAT_PNG: &[u8] = include_bytes!("../tests/fixtures/split_idat.png");
+
+    #[test]
+    fn test_chunk_reader_matches_try_from() {
+        let chunks: Vec<Chunk> = ChunkReader::new(SPLIT_IDAT_PNG)
+            .collect::<Result<_>>()
+            .unwrap();
+
+        assert_eq!(chunks, Png::try_from(SPLIT_IDAT_PNG).unwrap().chunks());
+    }
+
+    #[test]
+    fn test_chunk_reader_stops_after_iend() {
+        let mut bytes = SPLIT_IDAT_PNG.to_vec();
+        bytes.extend_from_slice(b"trailing garbage");
+        let mut reader = ChunkReader::new(Cursor::new(bytes));
+
+        assert_eq!(reader.by_ref().count(), 5);
+        assert!(reader.next().is_none());
+        assert_eq!(reader.into_inner().position() as usize, SPLIT_IDAT_PNG.len());
+    }
+
+    #[test]
+    fn test_chunk_reader_bad_signature() {
+        let mut reader = ChunkReader::new(&SPLIT_IDAT_PNG[1..]);
+
+        assert!(matches!(reader.next(), Some(Err(PngError::BadSignature))));
+        assert!(reader.next().is_none());
+    }
+}
@@ -0,0 +1,126 @@
+use crate::chunk::Chunk;
+use crate::chunk_type::ChunkType;
+use crate::error::{PngError, Result};
+
+/// A chunk whose data borrows from the buffer it was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkRef<'a> {
+    chunk_type: ChunkType,
+    data: &'a [u8],
+    crc: u32,
+}
+
+impl<'a> ChunkRef<'a> {
+    pub fn length(&self) -> u32 {
+        self.data.len() as u32
+    }
+
+    pub fn chunk_type(&self) -> &ChunkType {
+        &self.chunk_type
+    }
+
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn crc(&self) -> u32 {
+        self.crc
+    }
+}
+
+impl<'a> ChunkRef<'a> {
+    /// Parses a chunk like `try_from`, but keeps the stored CRC without
+    /// checking it against the data.
+    pub(crate) fn parse_unverified(bytes: &'a [u8]) -> Result<Self> {
+        if bytes.len() < 12 {
+            return Err(PngError::BadLength { expected: 12, found: bytes.len() });
+        }
+
+        let mut rest = bytes;
+        let data_length = u32::from_be_bytes(take_array(&mut rest)?);
+        if data_length > Chunk::MAX_LENGTH {
+            return Err(PngError::LengthTooLarge(data_length));
+        }
+
+        let expected_len = 12 + data_length as usize;
+        if bytes.len() < expected_len {
+            return Err(PngError::BadLength { expected: expected_len, found: bytes.len() });
+        }
+
+        let chunk_type = ChunkType::try_from(take_array(&mut rest)?)?;
+        let data = take(&mut rest, data_length as usize)?;
+        let crc = u32::from_be_bytes(take_array(&mut rest)?);
+
+        Ok(Self { chunk_type, data, crc })
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for ChunkRef<'a> {
+    type Error = PngError;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self> {
+        let chunk = Self::parse_unverified(bytes)?;
+        let computed_crc = Chunk::calculate_crc(chunk.chunk_type(), chunk.data());
+        if chunk.crc() != computed_crc {
+            return Err(PngError::CrcMismatch { expected: computed_crc, actual: chunk.crc() });
+        }
+
+        Ok(chunk)
+    }
+}
+
+/// Splits `n` bytes off the front of `bytes`, erroring instead of panicking
+/// when there aren't enough.
+fn take<'a>(bytes: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+    let (head, tail) = bytes
+        .split_at_checked(n)
+        .ok_or(PngError::BadLength { expected: n, found: bytes.len() })?;
+    *bytes = tail;
+    Ok(head)
+}
+
+fn take_array<const N: usize>(bytes: &mut &[u8]) -> Result<[u8; N]> {
+    let mut array = [0; N];
+    array.copy_from_slice(take(bytes, N)?);
+    Ok(array)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_chunk_ref_matches_chunk() {
+        let chunk = Chunk::new(ChunkType::from_str("RuSt").unwrap(), b"borrowed".to_vec());
+        let bytes = chunk.as_bytes();
+        let chunk_ref = ChunkRef::try_from(bytes.as_slice()).unwrap();
+
+        assert_eq!(chunk_ref.length(), chunk.length());
+        assert_eq!(chunk_ref.chunk_type(), chunk.chunk_type());
+        assert_eq!(chunk_ref.data(), chunk.data());
+        assert_eq!(chunk_ref.crc(), chunk.crc());
+        assert_eq!(Chunk::from(chunk_ref), chunk);
+    }
+
+    #[test]
+    fn test_chunk_ref_borrows_buffer() {
+        let chunk = Chunk::new(ChunkType::from_str("RuSt").unwrap(), b"borrowed".to_vec());
+        let bytes = chunk.as_bytes();
+        let chunk_ref = ChunkRef::try_from(bytes.as_slice()).unwrap();
+
+        assert_eq!(chunk_ref.data().as_ptr(), bytes[8..].as_ptr());
+    }
+
+    #[test]
+    fn test_chunk_ref_bad_crc() {
+        let chunk = Chunk::new(ChunkType::from_str("RuSt").unwrap(), b"borrowed".to_vec());
+        let mut bytes = chunk.as_bytes();
+        bytes[8] ^= 1;
+
+        assert!(matches!(
+            ChunkRef::try_from(bytes.as_slice()),
+            Err(PngError::CrcMismatch { .. })
+        ));
+    }
+}
@@ -1,19 +1,27 @@
-use std::str::FromStr;
-use std::fmt::Display;
+use alloc::string::{String, ToString};
+use core::str::FromStr;
+use core::fmt::Display;
 
-use anyhow::Ok;
-#[derive(Debug, Clone, PartialEq)]
+use crate::error::PngError;
+#[derive(Debug, Clone, PartialEq, PartialOrd, Ord, Hash)]
 pub struct ChunkType{
     bytes:[u8;4]
 }
 
 impl ChunkType {
+    /// Builds a `ChunkType` without validating `bytes`, so it can be used in
+    /// `const` items. The caller is responsible for passing four ASCII
+    /// letters; prefer `TryFrom<[u8; 4]>` for anything not known up front.
+    pub const fn new_unchecked(bytes: [u8; 4]) -> ChunkType {
+        ChunkType { bytes }
+    }
+
     pub fn bytes(&self) -> [u8; 4]{
-        self.bytes.clone()
+        self.bytes
     }
 
     #[rustfmt::skip]
-    fn is_valid(&self) -> bool{
+    pub fn is_valid(&self) -> bool{
         self.is_reserved_bit_valid() &&
         ChunkType::is_valid_byte(self.bytes[0]) &&
         ChunkType::is_valid_byte(self.bytes[1]) &&
@@ -23,67 +31,161 @@ impl ChunkType {
 
     #[rustfmt::skip]
     pub fn is_valid_byte(byte: u8) -> bool {
-        (byte >= 65 && byte <= 90) ||
-        (byte >= 97 && byte <= 122)
+        (65..=90).contains(&byte) ||
+        (97..=122).contains(&byte)
     }
 
-    fn is_critical(&self) -> bool{
+    pub fn is_critical(&self) -> bool{
         self.bytes[0].is_ascii_uppercase()
     }
 
-    fn is_public(&self) -> bool{
+    pub fn is_ancillary(&self) -> bool {
+        !self.is_critical()
+    }
+
+    pub fn is_public(&self) -> bool{
         self.bytes[1].is_ascii_uppercase()
     }
 
-    fn is_reserved_bit_valid(&self) -> bool{
+    pub fn is_private(&self) -> bool {
+        !self.is_public()
+    }
+
+    pub fn is_reserved_bit_valid(&self) -> bool{
         self.bytes[2].is_ascii_uppercase()
     }
 
-    fn is_safe_to_copy(&self) -> bool{
+    pub fn is_safe_to_copy(&self) -> bool{
         self.bytes[3].is_ascii_lowercase()
     }
 
+    /// The four property bits as (critical, public, reserved_valid,
+    /// safe_to_copy).
+    pub fn property_bits(&self) -> (bool, bool, bool, bool) {
+        (
+            self.is_critical(),
+            self.is_public(),
+            self.is_reserved_bit_valid(),
+            self.is_safe_to_copy(),
+        )
+    }
+
+    /// A copy with the third byte uppercased so the reserved bit is valid.
+    /// The other property bits are left alone.
+    pub fn with_reserved_bit_valid(&self) -> ChunkType {
+        let mut bytes = self.bytes;
+        bytes[2] = bytes[2].to_ascii_uppercase();
+        ChunkType { bytes }
+    }
+
+    /// Compares chunk types while ignoring the case (property) bits, so
+    /// `gAMA` and `GAMA` match. `==` stays byte-exact.
+    pub fn eq_ignore_case(&self, other: &ChunkType) -> bool {
+        self.bytes.eq_ignore_ascii_case(&other.bytes)
+    }
+
+    pub fn properties(&self) -> ChunkProperties {
+        ChunkProperties {
+            critical: self.is_critical(),
+            public: self.is_public(),
+            reserved_bit_valid: self.is_reserved_bit_valid(),
+            safe_to_copy: self.is_safe_to_copy(),
+        }
+    }
+
+}
+
+/// All four property bits of a chunk type, decoded from the case of each byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkProperties {
+    /// Uppercase first byte; ancillary otherwise.
+    pub critical: bool,
+    /// Uppercase second byte; private otherwise.
+    pub public: bool,
+    /// Uppercase third byte, the only value the spec currently allows.
+    pub reserved_bit_valid: bool,
+    /// Lowercase fourth byte; unsafe to copy otherwise.
+    pub safe_to_copy: bool,
 }
 
 impl TryFrom<[u8;4]> for ChunkType {
-    type Error = anyhow::Error;
+    type Error = PngError;
     fn try_from(value: [u8;4]) -> Result<Self, Self::Error> {
         for byte in value.iter(){
             if !ChunkType::is_valid_byte(*byte){
-                anyhow::bail!("Invalid byte {}. Valid bytes are ASCII A-Z and a-z, or 65-90 and 97-122",*byte)
+                return Err(PngError::InvalidByte(*byte));
             }
         }
         Ok(Self{bytes:value})
     }
 }
 impl FromStr for ChunkType {
-    type Err = anyhow::Error;
+    type Err = PngError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let bytes=s.as_bytes();
-        if bytes.len()!=4&&!s.is_ascii(){
-            anyhow::bail!("String must be 4 ASCII bytes")
+        let length = s.chars().count();
+        if length != 4 {
+            return Err(PngError::BadChunkTypeLength(length));
         }
-        Ok(Self::try_from([bytes[0],bytes[1],bytes[2],bytes[3]])?)
+        let Ok(bytes) = <[u8; 4]>::try_from(s.as_bytes()) else {
+            // Four characters but more than four bytes, so some are non-ASCII.
+            let byte = s.bytes().find(|b| !b.is_ascii()).unwrap_or_default();
+            return Err(PngError::InvalidByte(byte));
+        };
+        Self::try_from(bytes)
+    }
+}
+
+impl From<ChunkType> for [u8; 4] {
+    fn from(chunk_type: ChunkType) -> Self {
+        chunk_type.bytes
+    }
+}
+
+impl From<&ChunkType> for String {
+    fn from(chunk_type: &ChunkType) -> Self {
+        chunk_type.to_string()
     }
 }
 
 impl Display for ChunkType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
             f,
             "{}",
-            std::str::from_utf8(&self.bytes).expect("This is already validated as ASCII")
+            core::str::from_utf8(&self.bytes).expect("This is already validated as ASCII")
         )
     }
 }
 
 
+/// `aaAa`: an ancillary, private, safe-to-copy placeholder that can't be
+/// mistaken for a chunk with meaning. (`aaaa` would fail `is_valid`, since
+/// the reserved bit must be uppercase.)
+impl Default for ChunkType {
+    fn default() -> Self {
+        ChunkType::new_unchecked(*b"aaAa")
+    }
+}
+
 impl Eq for ChunkType {}
 
+impl PartialEq<str> for ChunkType {
+    fn eq(&self, other: &str) -> bool {
+        self.bytes.as_slice() == other.as_bytes()
+    }
+}
+
+impl PartialEq<[u8; 4]> for ChunkType {
+    fn eq(&self, other: &[u8; 4]) -> bool {
+        self.bytes == *other
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
     use std::convert::TryFrom;
     use std::str::FromStr;
 
@@ -98,12 +200,66 @@ mod tests {
     #[test]
     pub fn test_chunk_type_from_str() {
         let expected = ChunkType::try_from([82, 117, 83, 116]).unwrap();
-        let actual = ChunkType::from_str("Rut").unwrap();
-        println!("***********************");
-        println!("{actual}");
-        println!("***********************");
+        let actual = ChunkType::from_str("RuSt").unwrap();
+
+        assert_eq!(expected, actual);
+    }
 
-        //assert_eq!(expected, actual);
+    #[test]
+    pub fn test_chunk_type_from_empty_str() {
+        assert!(ChunkType::from_str("").is_err());
+    }
+
+    #[test]
+    pub fn test_chunk_type_from_short_str() {
+        assert!(ChunkType::from_str("Rut").is_err());
+    }
+
+    #[test]
+    pub fn test_chunk_type_from_long_str() {
+        assert!(ChunkType::from_str("RuStt").is_err());
+    }
+
+    #[test]
+    pub fn test_chunk_type_errors() {
+        assert!(matches!(
+            ChunkType::from_str("Rut"),
+            Err(PngError::BadChunkTypeLength(3))
+        ));
+        assert!(matches!(
+            ChunkType::from_str("Ru1t"),
+            Err(PngError::InvalidByte(b'1'))
+        ));
+    }
+
+    #[test]
+    pub fn test_chunk_type_error_messages() {
+        assert_eq!(
+            ChunkType::from_str("RuStt").unwrap_err().to_string(),
+            "chunk type must be exactly 4 characters, got 5"
+        );
+        assert_eq!(
+            ChunkType::from_str("Ru1t").unwrap_err().to_string(),
+            "invalid byte 49: valid bytes are ASCII A-Z and a-z, or 65-90 and 97-122"
+        );
+    }
+
+    #[test]
+    pub fn test_chunk_type_from_multibyte_str() {
+        // Lengths count characters, not bytes.
+        assert!(matches!(
+            ChunkType::from_str("Ru€"),
+            Err(PngError::BadChunkTypeLength(3))
+        ));
+        assert!(matches!(
+            ChunkType::from_str("R€"),
+            Err(PngError::BadChunkTypeLength(2))
+        ));
+        // Four characters, but the first byte of '€' is not ASCII.
+        assert!(matches!(
+            ChunkType::from_str("Ru€t"),
+            Err(PngError::InvalidByte(0xE2))
+        ));
     }
 
     #[test]
@@ -119,6 +275,19 @@ mod tests {
         assert!(!chunk.is_critical());
     }
 
+    #[test]
+    pub fn test_chunk_type_is_ancillary() {
+        assert!(ChunkType::from_str("tEXt").unwrap().is_ancillary());
+        assert!(!ChunkType::from_str("IHDR").unwrap().is_ancillary());
+    }
+
+    #[test]
+    pub fn test_chunk_type_is_private() {
+        assert!(!ChunkType::from_str("tEXt").unwrap().is_private());
+        assert!(!ChunkType::from_str("IHDR").unwrap().is_private());
+        assert!(ChunkType::from_str("ruSt").unwrap().is_private());
+    }
+
     #[test]
     pub fn test_chunk_type_is_public() {
         let chunk = ChunkType::from_str("RUSt").unwrap();
@@ -155,6 +324,26 @@ mod tests {
         assert!(!chunk.is_safe_to_copy());
     }
 
+    #[test]
+    pub fn test_chunk_type_properties() {
+        let chunk = ChunkType::from_str("bLOb").unwrap();
+        assert_eq!(
+            chunk.properties(),
+            ChunkProperties {
+                critical: false,
+                public: true,
+                reserved_bit_valid: true,
+                safe_to_copy: true,
+            }
+        );
+
+        let properties = ChunkType::from_str("RuST").unwrap().properties();
+        assert!(properties.critical);
+        assert!(!properties.public);
+        assert!(properties.reserved_bit_valid);
+        assert!(!properties.safe_to_copy);
+    }
+
     #[test]
     pub fn test_valid_chunk_is_valid() {
         let chunk = ChunkType::from_str("RuSt").unwrap();
@@ -176,6 +365,99 @@ mod tests {
         assert_eq!(&chunk.to_string(), "RuSt");
     }
 
+    #[test]
+    pub fn test_chunk_type_hash_set() {
+        let mut chunk_types = HashSet::new();
+        chunk_types.insert(ChunkType::from_str("IHDR").unwrap());
+        chunk_types.insert(ChunkType::from_str("IDAT").unwrap());
+        chunk_types.insert(ChunkType::from_str("IDAT").unwrap());
+
+        assert_eq!(chunk_types.len(), 2);
+        assert!(chunk_types.contains(&ChunkType::from_str("IHDR").unwrap()));
+        assert!(chunk_types.contains(&ChunkType::from_str("IDAT").unwrap()));
+        assert!(!chunk_types.contains(&ChunkType::from_str("IEND").unwrap()));
+    }
+
+    #[test]
+    pub fn test_chunk_type_sort() {
+        let mut chunk_types: Vec<ChunkType> = ["IHDR", "tEXt", "IEND", "IDAT"]
+            .iter()
+            .map(|s| ChunkType::from_str(s).unwrap())
+            .collect();
+        chunk_types.sort();
+
+        let sorted: Vec<String> = chunk_types.iter().map(|c| c.to_string()).collect();
+        assert_eq!(sorted, ["IDAT", "IEND", "IHDR", "tEXt"]);
+        assert!(chunk_types.windows(2).all(|w| w[0].bytes() < w[1].bytes()));
+    }
+
+    #[test]
+    pub fn test_chunk_type_new_unchecked() {
+        const IHDR: ChunkType = ChunkType::new_unchecked(*b"IHDR");
+        const IEND: ChunkType = ChunkType::new_unchecked(*b"IEND");
+
+        assert_eq!(IHDR, ChunkType::from_str("IHDR").unwrap());
+        assert_eq!(IEND, ChunkType::from_str("IEND").unwrap());
+    }
+
+    #[test]
+    pub fn test_chunk_type_into_bytes() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let bytes: [u8; 4] = chunk_type.clone().into();
+
+        assert_eq!(bytes, *b"RuSt");
+        assert_eq!(ChunkType::try_from(bytes).unwrap(), chunk_type);
+    }
+
+    #[test]
+    pub fn test_chunk_type_into_string() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let string: String = (&chunk_type).into();
+
+        assert_eq!(string, "RuSt");
+        assert_eq!(ChunkType::from_str(&string).unwrap(), chunk_type);
+    }
+
+    #[test]
+    pub fn test_chunk_type_with_reserved_bit_valid() {
+        let chunk_type = ChunkType::from_str("Rust").unwrap();
+        assert!(!chunk_type.is_reserved_bit_valid());
+
+        let repaired = chunk_type.with_reserved_bit_valid();
+        assert_eq!(repaired.to_string(), "RuSt");
+        assert!(repaired.is_reserved_bit_valid());
+        assert_eq!(repaired.is_critical(), chunk_type.is_critical());
+        assert_eq!(repaired.is_public(), chunk_type.is_public());
+        assert_eq!(repaired.is_safe_to_copy(), chunk_type.is_safe_to_copy());
+
+        let valid = ChunkType::from_str("ruSt").unwrap();
+        assert_eq!(valid.with_reserved_bit_valid(), valid);
+    }
+
+    #[test]
+    pub fn test_chunk_type_eq_ignore_case() {
+        let lower = ChunkType::from_str("gAMA").unwrap();
+        let upper = ChunkType::from_str("GAMA").unwrap();
+
+        assert!(lower.eq_ignore_case(&upper));
+        assert!(upper.eq_ignore_case(&lower));
+        assert_ne!(lower, upper);
+        assert!(!lower.eq_ignore_case(&ChunkType::from_str("gAMB").unwrap()));
+    }
+
+    #[test]
+    pub fn test_chunk_type_eq_literals() {
+        let chunk_type = ChunkType::from_str("IHDR").unwrap();
+
+        assert!(chunk_type == *"IHDR");
+        assert!(&chunk_type == "IHDR");
+        assert!(chunk_type != *"IDAT");
+        assert!(chunk_type != *"IHD");
+        assert!(chunk_type == *b"IHDR");
+        assert!(&chunk_type == b"IHDR");
+        assert!(chunk_type != *b"ihdr");
+    }
+
     #[test]
     pub fn test_chunk_type_trait_impls() {
         let chunk_type_1: ChunkType = TryFrom::try_from([82, 117, 83, 116]).unwrap();
@@ -183,4 +465,26 @@ mod tests {
         let _chunk_string = format!("{}", chunk_type_1);
         let _are_chunks_equal = chunk_type_1 == chunk_type_2;
     }
+
+    #[test]
+    fn test_property_bits() {
+        assert_eq!(ChunkType::from_str("RuSt").unwrap().property_bits(), (true, false, true, true));
+        assert_eq!(ChunkType::from_str("IDAT").unwrap().property_bits(), (true, true, true, false));
+    }
+
+    #[test]
+    fn test_default_chunk_type() {
+        let chunk_type = ChunkType::default();
+
+        assert!(chunk_type.is_valid());
+        assert!(chunk_type.is_ancillary());
+        assert!(chunk_type.is_private());
+        assert_eq!(chunk_type.to_string(), "aaAa");
+    }
+
+    #[test]
+    fn test_chunk_type_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<ChunkType>();
+    }
 }
@@ -0,0 +1,449 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::Context;
+use pngme::chunk::Chunk;
+use pngme::chunk_type::ChunkType;
+use pngme::diff::ChunkDiff;
+use pngme::png::Png;
+
+use crate::args::{
+    DecodeArgs, DiffArgs, EncodeArgs, ExtractArgs, ListArgs, OutputFormat, PrintArgs,
+    RemoveArgs, SanitizeArgs, VerifyArgs,
+};
+
+fn read_png(path: &Path) -> anyhow::Result<Png> {
+    let bytes = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    Png::try_from(bytes.as_slice()).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+fn write_png(path: &Path, png: &Png) -> anyhow::Result<()> {
+    fs::write(path, png.as_bytes()).with_context(|| format!("failed to write {}", path.display()))
+}
+
+pub fn encode(args: &EncodeArgs) -> anyhow::Result<()> {
+    let mut png = read_png(&args.file_path)?;
+    if args.chunk_type == STDIN_ARG && args.message.as_deref() == Some(STDIN_ARG) {
+        anyhow::bail!("only one of the chunk type and message can be read from stdin");
+    }
+    let mut stdin = io::stdin().lock();
+    let chunk_type = arg_or_stdin(&args.chunk_type, &mut stdin)?;
+    let chunk_type = ChunkType::from_str(&chunk_type)
+        .with_context(|| format!("invalid chunk type {chunk_type:?}"))?;
+    if chunk_type.is_critical() && !args.force {
+        anyhow::bail!(
+            "{chunk_type} is a critical chunk type and most decoders will reject the file; \
+             use a type starting with a lowercase letter or pass --force"
+        );
+    }
+    let payload = match (&args.file, &args.message) {
+        (Some(path), _) => {
+            fs::read(path).with_context(|| format!("failed to read {}", path.display()))?
+        }
+        (None, Some(message)) => arg_or_stdin(message, &mut stdin)?.into_bytes(),
+        (None, None) => anyhow::bail!("either a message or --file is required"),
+    };
+    let chunk = if args.compress {
+        Chunk::new_compressed(chunk_type, &payload)?
+    } else {
+        Chunk::try_new(chunk_type, payload)?
+    };
+    match args.at {
+        Some(index) => png.insert_chunk(index, chunk)?,
+        None => png.append_chunk(chunk),
+    }
+
+    if args.dry_run {
+        for chunk in png.chunks() {
+            println!("{}", chunk);
+        }
+        println!("New file size: {} bytes", png.total_byte_size());
+        return Ok(());
+    }
+
+    write_png(args.output.as_ref().unwrap_or(&args.file_path), &png)
+}
+
+/// The argument value that means "read this from stdin".
+const STDIN_ARG: &str = "-";
+
+/// Returns `value`, or everything on `stdin` minus one trailing newline if
+/// `value` is `-`.
+fn arg_or_stdin(value: &str, stdin: &mut impl Read) -> anyhow::Result<String> {
+    if value != STDIN_ARG {
+        return Ok(value.to_string());
+    }
+
+    let mut input = String::new();
+    stdin.read_to_string(&mut input).context("failed to read stdin")?;
+    let trimmed = input.strip_suffix('\n').unwrap_or(&input);
+    let trimmed = trimmed.strip_suffix('\r').unwrap_or(trimmed);
+    Ok(trimmed.to_string())
+}
+
+pub fn decode(args: &DecodeArgs) -> anyhow::Result<()> {
+    if let Some(out) = &args.out {
+        return fs::write(out, decoded_data(args)?)
+            .with_context(|| format!("failed to write {}", out.display()));
+    }
+
+    println!("{}", decoded_message(args)?);
+    Ok(())
+}
+
+/// The chunk's data as `decode` outputs it, printed or written with `--out`.
+/// Data carrying the marker `encode --compress` writes is inflated unless
+/// `--raw` is given. Data without the marker is never inflated, even if it
+/// is a valid zlib stream.
+fn decoded_data(args: &DecodeArgs) -> anyhow::Result<Vec<u8>> {
+    let png = read_png(&args.file_path)?;
+    let chunk = png
+        .chunk_by_type(&args.chunk_type)
+        .with_context(|| format!("no {} chunk found", args.chunk_type))?;
+
+    if !args.raw && chunk.is_compressed() {
+        return Ok(chunk.data_decompressed()?);
+    }
+    Ok(chunk.data().to_vec())
+}
+
+fn decoded_message(args: &DecodeArgs) -> anyhow::Result<String> {
+    String::from_utf8(decoded_data(args)?).context("chunk data is not valid UTF-8")
+}
+
+pub fn remove(args: &RemoveArgs) -> anyhow::Result<()> {
+    let mut png = read_png(&args.file_path)?;
+    let chunk = png.remove_first_chunk(&args.chunk_type)?;
+    write_png(&args.file_path, &png)?;
+
+    println!("Removed {} chunk ({} bytes)", chunk.chunk_type(), chunk.length());
+    Ok(())
+}
+
+pub fn extract(args: &ExtractArgs) -> anyhow::Result<()> {
+    let png = read_png(&args.file_path)?;
+    let chunk = png
+        .chunk_by_type(&args.chunk_type)
+        .with_context(|| format!("no {} chunk found", args.chunk_type))?;
+
+    fs::write(&args.output, chunk.data())
+        .with_context(|| format!("failed to write {}", args.output.display()))
+}
+
+pub fn list(args: &ListArgs) -> anyhow::Result<()> {
+    for (chunk_type, count) in chunk_type_counts(&read_png(&args.file_path)?) {
+        println!("{chunk_type} {count}");
+    }
+    Ok(())
+}
+
+fn chunk_type_counts(png: &Png) -> Vec<(ChunkType, usize)> {
+    let mut counts: HashMap<ChunkType, usize> = HashMap::new();
+    for chunk in png.chunks() {
+        *counts.entry(chunk.chunk_type().clone()).or_default() += 1;
+    }
+
+    let mut counts: Vec<(ChunkType, usize)> = counts.into_iter().collect();
+    counts.sort();
+    counts
+}
+
+pub fn verify(args: &VerifyArgs) -> anyhow::Result<()> {
+    let bytes = fs::read(&args.file_path)
+        .with_context(|| format!("failed to read {}", args.file_path.display()))?;
+    // Parse without CRC checks so a bad CRC is reported with its chunk index.
+    let png = Png::from_bytes_unchecked(&bytes)
+        .with_context(|| format!("failed to parse {}", args.file_path.display()))?;
+    png.verify_crcs()?;
+    png.validate_structure()?;
+
+    println!("OK");
+    Ok(())
+}
+
+pub fn sanitize(args: &SanitizeArgs) -> anyhow::Result<()> {
+    let mut png = read_png(&args.file_path)?;
+    let before = png.chunks().len();
+    // Critical chunks are always kept: the image can't be decoded without
+    // them, whatever their property bits say.
+    png.retain_chunks(|chunk| {
+        let chunk_type = chunk.chunk_type();
+        chunk_type.is_critical() || (chunk_type.is_public() && chunk_type.is_safe_to_copy())
+    });
+    let removed = before - png.chunks().len();
+    write_png(&args.file_path, &png)?;
+
+    println!("Removed {removed} chunks");
+    Ok(())
+}
+
+pub fn diff(args: &DiffArgs) -> anyhow::Result<()> {
+    let old = read_png(&args.old_path)?;
+    let new = read_png(&args.new_path)?;
+    for diff in old.diff(&new) {
+        match diff {
+            ChunkDiff::Added { index, chunk_type } => println!("+ {index} {chunk_type}"),
+            ChunkDiff::Removed { index, chunk_type } => println!("- {index} {chunk_type}"),
+            ChunkDiff::DataChanged { old_index, new_index, chunk_type } => {
+                println!("~ {old_index} -> {new_index} {chunk_type}")
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn print(args: &PrintArgs) -> anyhow::Result<()> {
+    let png = read_png(&args.file_path)?;
+    let selected: Vec<(&Chunk, usize)> = png
+        .chunks()
+        .iter()
+        .zip(png.serialized_offsets())
+        .filter(|(chunk, _)| {
+            args.chunk_type
+                .as_ref()
+                .is_none_or(|chunk_type| chunk.chunk_type() == chunk_type.as_str())
+        })
+        .collect();
+
+    match args.format {
+        OutputFormat::Text => {
+            for (chunk, _) in selected {
+                println!("{}", chunk);
+                if args.data {
+                    print!("{}", hex_dump(chunk.data(), args.max_bytes));
+                }
+            }
+        }
+        OutputFormat::Json => println!("{}", chunks_json(&selected)),
+    }
+    Ok(())
+}
+
+/// Formats `data` like `hexdump -C`: an offset column, 16 bytes in hex, and
+/// their printable ASCII. Only the first `max_bytes` bytes are shown.
+fn hex_dump(data: &[u8], max_bytes: usize) -> String {
+    let shown = &data[..data.len().min(max_bytes)];
+    let mut dump = String::new();
+    for (line, bytes) in shown.chunks(16).enumerate() {
+        let hex: Vec<String> = (0..16)
+            .map(|i| bytes.get(i).map_or("  ".to_string(), |b| format!("{b:02x}")))
+            .collect();
+        let ascii: String = bytes
+            .iter()
+            .map(|&b| if b.is_ascii_graphic() || b == b' ' { b as char } else { '.' })
+            .collect();
+        dump += &format!(
+            "{:08x}  {}  {}  |{}|\n",
+            line * 16,
+            hex[..8].join(" "),
+            hex[8..].join(" "),
+            ascii
+        );
+    }
+    if shown.len() < data.len() {
+        dump += &format!("... {} more bytes\n", data.len() - shown.len());
+    }
+    dump
+}
+
+fn chunks_json(chunks: &[(&Chunk, usize)]) -> String {
+    let entries: Vec<String> = chunks
+        .iter()
+        .map(|(chunk, offset)| {
+            // Chunk types are always ASCII letters, so nothing needs escaping.
+            format!(
+                r#"{{"type":"{}","length":{},"crc":"{:08x}","offset":{}}}"#,
+                chunk.chunk_type(),
+                chunk.length(),
+                chunk.crc(),
+                offset
+            )
+        })
+        .collect();
+
+    format!("[{}]", entries.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    const BASIC_PNG: &[u8] = include_bytes!("../tests/fixtures/basic.png");
+
+    fn scratch_png(dir: &TempDir) -> PathBuf {
+        let path = dir.path().join("image.png");
+        fs::write(&path, BASIC_PNG).unwrap();
+        path
+    }
+
+    fn encode_args(file_path: PathBuf, message: &str) -> EncodeArgs {
+        EncodeArgs {
+            file_path,
+            chunk_type: "ruSt".to_string(),
+            message: Some(message.to_string()),
+            file: None,
+            output: None,
+            compress: false,
+            dry_run: false,
+            at: None,
+            force: false,
+        }
+    }
+
+    #[test]
+    fn test_encode_then_decode() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = scratch_png(&dir);
+
+        encode(&encode_args(file_path.clone(), "This is a secret")).unwrap();
+
+        let args = DecodeArgs {
+            file_path,
+            chunk_type: "ruSt".to_string(),
+            out: None,
+            raw: false,
+        };
+        decode(&args).unwrap();
+        assert_eq!(decoded_message(&args).unwrap(), "This is a secret");
+    }
+
+    #[test]
+    fn test_encode_compressed_then_decode() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = scratch_png(&dir);
+        let message = "a highly compressible message ".repeat(100);
+
+        let mut args = encode_args(file_path.clone(), &message);
+        args.compress = true;
+        encode(&args).unwrap();
+
+        let png = read_png(&file_path).unwrap();
+        assert!((png.chunk_by_type("ruSt").unwrap().length() as usize) < message.len());
+
+        let args = DecodeArgs {
+            file_path,
+            chunk_type: "ruSt".to_string(),
+            out: None,
+            raw: false,
+        };
+        assert_eq!(decoded_message(&args).unwrap(), message);
+    }
+
+    #[test]
+    fn test_decode_plain_text_resembling_zlib() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = scratch_png(&dir);
+
+        // "x " happens to be a valid zlib header.
+        encode(&encode_args(file_path.clone(), "x marks the spot")).unwrap();
+
+        let args = DecodeArgs {
+            file_path,
+            chunk_type: "ruSt".to_string(),
+            out: None,
+            raw: false,
+        };
+        assert_eq!(decoded_message(&args).unwrap(), "x marks the spot");
+    }
+
+    #[test]
+    fn test_decode_missing_chunk() {
+        let dir = tempfile::tempdir().unwrap();
+        let args = DecodeArgs {
+            file_path: scratch_png(&dir),
+            chunk_type: "ruSt".to_string(),
+            out: None,
+            raw: false,
+        };
+
+        assert!(decode(&args).is_err());
+    }
+
+    #[test]
+    fn test_encode_then_remove() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = scratch_png(&dir);
+
+        encode(&encode_args(file_path.clone(), "This is a secret")).unwrap();
+        remove(&RemoveArgs {
+            file_path: file_path.clone(),
+            chunk_type: "ruSt".to_string(),
+        })
+        .unwrap();
+
+        assert_eq!(fs::read(&file_path).unwrap(), BASIC_PNG);
+    }
+
+    #[test]
+    fn test_encode_invalid_chunk_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let file_path = scratch_png(&dir);
+        let mut args = encode_args(file_path.clone(), "This is a secret");
+        args.chunk_type = "ru5t".to_string();
+
+        assert!(encode(&args).is_err());
+        assert_eq!(fs::read(&file_path).unwrap(), BASIC_PNG);
+    }
+
+    #[test]
+    fn test_hex_dump() {
+        let data = b"Hello, hex dump!\x00\x01\x7f\xff";
+
+        assert_eq!(
+            hex_dump(data, 256),
+            "00000000  48 65 6c 6c 6f 2c 20 68  65 78 20 64 75 6d 70 21  |Hello, hex dump!|\n\
+             00000010  00 01 7f ff                                       |....|\n"
+        );
+    }
+
+    #[test]
+    fn test_hex_dump_truncated() {
+        let data = [0xAB; 20];
+
+        assert_eq!(
+            hex_dump(&data, 4),
+            "00000000  ab ab ab ab                                       |....|\n\
+             ... 16 more bytes\n"
+        );
+        assert_eq!(hex_dump(&[], 256), "");
+    }
+
+    #[test]
+    fn test_chunk_type_counts() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.append_chunk(Chunk::new(ChunkType::from_str("ruSt").unwrap(), Vec::new()));
+        png.append_chunk(Chunk::new(ChunkType::from_str("ruSt").unwrap(), Vec::new()));
+
+        let counts: Vec<(String, usize)> = chunk_type_counts(&png)
+            .into_iter()
+            .map(|(chunk_type, count)| (chunk_type.to_string(), count))
+            .collect();
+        assert_eq!(
+            counts,
+            [
+                ("IDAT".to_string(), 1),
+                ("IEND".to_string(), 1),
+                ("IHDR".to_string(), 1),
+                ("ruSt".to_string(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_arg_or_stdin() {
+        let mut stdin = "from stdin\n".as_bytes();
+        assert_eq!(arg_or_stdin("literal", &mut stdin).unwrap(), "literal");
+        assert_eq!(arg_or_stdin("-", &mut stdin).unwrap(), "from stdin");
+
+        let mut stdin = "two lines\r\n\n".as_bytes();
+        assert_eq!(arg_or_stdin("-", &mut stdin).unwrap(), "two lines\r\n");
+
+        let mut stdin = "windows\r\n".as_bytes();
+        assert_eq!(arg_or_stdin("-", &mut stdin).unwrap(), "windows");
+    }
+}
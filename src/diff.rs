@@ -0,0 +1,119 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::chunk_type::ChunkType;
+use crate::png::Png;
+
+/// One difference between two PNGs, as reported by [`Png::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChunkDiff {
+    /// A chunk at `index` in the other PNG with no counterpart in this one.
+    Added { index: usize, chunk_type: ChunkType },
+    /// A chunk at `index` in this PNG with no counterpart in the other.
+    Removed { index: usize, chunk_type: ChunkType },
+    /// Chunks of the same type that line up but hold different data.
+    DataChanged { old_index: usize, new_index: usize, chunk_type: ChunkType },
+}
+
+impl Png {
+    /// Lists the chunks added, removed, or changed going from `self` to
+    /// `other`. Chunks are lined up by type using a longest common
+    /// subsequence, so an inserted chunk doesn't make everything after it
+    /// look changed.
+    pub fn diff(&self, other: &Png) -> Vec<ChunkDiff> {
+        let (old, new) = (self.chunks(), other.chunks());
+        let same_type = |i: usize, j: usize| old[i].chunk_type() == new[j].chunk_type();
+
+        // lcs[i][j] is the LCS length of old[i..] and new[j..].
+        let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+        for i in (0..old.len()).rev() {
+            for j in (0..new.len()).rev() {
+                lcs[i][j] = if same_type(i, j) {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        let mut diffs = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < old.len() || j < new.len() {
+            if i < old.len() && j < new.len() && same_type(i, j) {
+                if old[i].data() != new[j].data() {
+                    diffs.push(ChunkDiff::DataChanged {
+                        old_index: i,
+                        new_index: j,
+                        chunk_type: old[i].chunk_type().clone(),
+                    });
+                }
+                i += 1;
+                j += 1;
+            } else if j == new.len() || (i < old.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+                diffs.push(ChunkDiff::Removed { index: i, chunk_type: old[i].chunk_type().clone() });
+                i += 1;
+            } else {
+                diffs.push(ChunkDiff::Added { index: j, chunk_type: new[j].chunk_type().clone() });
+                j += 1;
+            }
+        }
+        diffs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk::Chunk;
+    use std::str::FromStr;
+
+    const BASIC_PNG: &[u8] = include_bytes!("../tests/fixtures/basic.png");
+
+    fn rust_chunk(message: &str) -> Chunk {
+        Chunk::new(ChunkType::from_str("ruSt").unwrap(), message.as_bytes().to_vec())
+    }
+
+    #[test]
+    fn test_diff_after_encode() {
+        let original = Png::try_from(BASIC_PNG).unwrap();
+        let mut encoded = original.clone();
+        encoded.append_chunk(rust_chunk("This is a secret"));
+
+        assert_eq!(
+            original.diff(&encoded),
+            [ChunkDiff::Added { index: 2, chunk_type: ChunkType::from_str("ruSt").unwrap() }]
+        );
+        assert_eq!(
+            encoded.diff(&original),
+            [ChunkDiff::Removed { index: 2, chunk_type: ChunkType::from_str("ruSt").unwrap() }]
+        );
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        let png = Png::try_from(BASIC_PNG).unwrap();
+
+        assert!(png.diff(&png).is_empty());
+    }
+
+    #[test]
+    fn test_diff_data_changed() {
+        let mut old = Png::try_from(BASIC_PNG).unwrap();
+        old.append_chunk(rust_chunk("first"));
+        old.append_chunk(Chunk::text("Comment", "gone").unwrap());
+        let mut new = Png::try_from(BASIC_PNG).unwrap();
+        new.insert_chunk(1, Chunk::text("Author", "Ferris").unwrap()).unwrap();
+        new.append_chunk(rust_chunk("second"));
+
+        let rust = ChunkType::from_str("ruSt").unwrap();
+        let text = ChunkType::from_str("tEXt").unwrap();
+        assert_eq!(
+            old.diff(&new),
+            [
+                ChunkDiff::Added { index: 1, chunk_type: text.clone() },
+                ChunkDiff::DataChanged { old_index: 2, new_index: 3, chunk_type: rust },
+                ChunkDiff::Removed { index: 3, chunk_type: text },
+            ]
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+
+use thiserror::Error;
+
+use crate::chunk_type::ChunkType;
+
+/// `Result` with [`PngError`] as the default error type.
+///
+/// ```
+/// use std::str::FromStr;
+/// use pngme::chunk_type::ChunkType;
+///
+/// fn parse(name: &str) -> pngme::Result<ChunkType> {
+///     ChunkType::from_str(name)
+/// }
+///
+/// assert!(parse("ruSt").is_ok());
+/// assert!(matches!(parse("ru5t"), Err(pngme::Error::InvalidByte(b'5'))));
+/// ```
+pub type Result<T, E = PngError> = core::result::Result<T, E>;
+
+#[derive(Debug, Error)]
+pub enum PngError {
+    #[error("invalid byte {0}: valid bytes are ASCII A-Z and a-z, or 65-90 and 97-122")]
+    InvalidByte(u8),
+
+    #[error("chunk type must be exactly 4 characters, got {0}")]
+    BadChunkTypeLength(usize),
+
+    #[error("bad length: expected {expected} bytes, found {found}")]
+    BadLength { expected: usize, found: usize },
+
+    /// `expected` is the CRC computed over the chunk type and data, `actual`
+    /// is the CRC stored in the chunk.
+    #[error("CRC mismatch: expected {expected:#010x}, found {actual:#010x}")]
+    CrcMismatch { expected: u32, actual: u32 },
+
+    #[error("CRC mismatch in chunk {index}: expected {expected:#010x}, found {actual:#010x}")]
+    ChunkCrcMismatch { index: usize, expected: u32, actual: u32 },
+
+    #[error("message of {0} bytes exceeds PNG chunk limit")]
+    PayloadTooLarge(usize),
+
+    #[error("chunk length {0} exceeds the PNG limit of 2^31-1 bytes")]
+    LengthTooLarge(u32),
+
+    #[error("expected a {expected} chunk, found {found}")]
+    WrongChunkType { expected: ChunkType, found: ChunkType },
+
+    #[error("malformed {chunk_type} chunk: {reason}")]
+    MalformedChunk { chunk_type: ChunkType, reason: &'static str },
+
+    #[error("invalid keyword: {0}")]
+    InvalidKeyword(String),
+
+    #[error("character {0:?} cannot be encoded as Latin-1")]
+    InvalidLatin1(char),
+
+    #[error("invalid PNG signature")]
+    BadSignature,
+
+    #[error("invalid PNG structure: {0}")]
+    Structure(String),
+
+    #[error("cannot insert a chunk at index {index}: {reason}")]
+    InvalidPosition { index: usize, reason: &'static str },
+
+    #[error("chunk index {index} is out of bounds for {len} chunks")]
+    IndexOutOfBounds { index: usize, len: usize },
+
+    #[error("chunk type {0} not found")]
+    ChunkNotFound(String),
+
+    /// Wraps the error from parsing one chunk of a PNG. Use
+    /// [`PngError::root`] to match on the underlying error.
+    #[error("failed parsing chunk at index {index}")]
+    ChunkParse { index: usize, source: Box<PngError> },
+
+    #[error("chunk data is not valid UTF-8")]
+    InvalidUtf8(#[from] alloc::string::FromUtf8Error),
+
+    #[cfg(feature = "std")]
+    #[error("failed to decompress chunk data: {0}")]
+    Decompress(std::io::Error),
+
+    #[cfg(feature = "std")]
+    #[error("{}", path.display())]
+    PathIo { path: std::path::PathBuf, source: std::io::Error },
+
+    #[cfg(feature = "std")]
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+impl PngError {
+    pub(crate) fn in_chunk(self, index: usize) -> PngError {
+        PngError::ChunkParse { index, source: Box::new(self) }
+    }
+
+    /// The error with any chunk index context stripped off.
+    pub fn root(&self) -> &PngError {
+        match self {
+            PngError::ChunkParse { source, .. } => source.root(),
+            other => other,
+        }
+    }
+}
+
+/// A non-fatal problem found by [`Png::from_bytes_lenient`](crate::png::Png::from_bytes_lenient).
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ParseWarning {
+    #[error("invalid PNG signature")]
+    BadSignature,
+
+    #[error("chunk {0} has the reserved bit set")]
+    ReservedBitSet(ChunkType),
+
+    #[error("unknown critical chunk {0}")]
+    UnknownCriticalChunk(ChunkType),
+
+    #[error("CRC mismatch in {chunk_type} chunk: expected {expected:#010x}, found {actual:#010x}")]
+    CrcMismatch { chunk_type: ChunkType, expected: u32, actual: u32 },
+
+    #[error("{0} bytes of data after IEND")]
+    DataAfterIend(usize),
+
+    #[error("{length} unreadable bytes at offset {offset}")]
+    UnreadableData { offset: usize, length: usize },
+}
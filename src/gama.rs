@@ -0,0 +1,71 @@
+use crate::chunk::Chunk;
+use crate::chunk_type::ChunkType;
+use crate::error::{PngError, Result};
+use crate::png::Png;
+
+pub const GAMA: ChunkType = ChunkType::new_unchecked(*b"gAMA");
+
+/// `gAMA` stores the image gamma times 100000.
+const GAMMA_SCALE: f64 = 100_000.0;
+
+impl Png {
+    /// The image gamma from the `gAMA` chunk, or `None` if there is no
+    /// `gAMA` chunk or it is malformed. See [`Png::try_gamma`] to tell
+    /// those apart.
+    pub fn gamma(&self) -> Option<f64> {
+        self.try_gamma().ok().flatten()
+    }
+
+    pub fn try_gamma(&self) -> Result<Option<f64>> {
+        let Some(chunk) = self.iter().find(|chunk| *chunk.chunk_type() == GAMA) else {
+            return Ok(None);
+        };
+        Ok(Some(gamma_from_chunk(chunk)?))
+    }
+}
+
+fn gamma_from_chunk(chunk: &Chunk) -> Result<f64> {
+    let data: [u8; 4] = chunk.data().try_into().map_err(|_| PngError::BadLength {
+        expected: 4,
+        found: chunk.data().len(),
+    })?;
+
+    Ok(u32::from_be_bytes(data) as f64 / GAMMA_SCALE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASIC_PNG: &[u8] = include_bytes!("../tests/fixtures/basic.png");
+    const GAMMA_PNG: &[u8] = include_bytes!("../tests/fixtures/gamma.png");
+
+    #[test]
+    fn test_gamma_from_fixture() {
+        let png = Png::try_from(GAMMA_PNG).unwrap();
+
+        assert_eq!(png.gamma(), Some(0.45455));
+        assert_eq!(png.try_gamma().unwrap(), Some(0.45455));
+        assert!((1.0 / png.gamma().unwrap() - 2.2).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_gamma_absent() {
+        let png = Png::try_from(BASIC_PNG).unwrap();
+
+        assert_eq!(png.gamma(), None);
+        assert_eq!(png.try_gamma().unwrap(), None);
+    }
+
+    #[test]
+    fn test_gamma_wrong_length() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.insert_chunk(1, Chunk::new(GAMA, vec![0, 0, 177])).unwrap();
+
+        assert_eq!(png.gamma(), None);
+        assert!(matches!(
+            png.try_gamma(),
+            Err(PngError::BadLength { expected: 4, found: 3 })
+        ));
+    }
+}
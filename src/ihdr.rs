@@ -0,0 +1,123 @@
+use alloc::vec::Vec;
+
+use crate::chunk::Chunk;
+use crate::chunk_type::ChunkType;
+use crate::error::{PngError, Result};
+
+pub const IHDR: ChunkType = ChunkType::new_unchecked(*b"IHDR");
+
+/// The image header stored in the 13-byte payload of an `IHDR` chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IhdrData {
+    pub width: u32,
+    pub height: u32,
+    pub bit_depth: u8,
+    pub color_type: u8,
+    pub compression: u8,
+    pub filter: u8,
+    pub interlace: u8,
+}
+
+impl IhdrData {
+    pub const LENGTH: usize = 13;
+}
+
+impl TryFrom<&Chunk> for IhdrData {
+    type Error = PngError;
+
+    fn try_from(chunk: &Chunk) -> Result<Self> {
+        if *chunk.chunk_type() != IHDR {
+            return Err(PngError::WrongChunkType {
+                expected: IHDR,
+                found: chunk.chunk_type().clone(),
+            });
+        }
+
+        let data: &[u8; Self::LENGTH] = chunk.data().try_into().map_err(|_| PngError::BadLength {
+            expected: Self::LENGTH,
+            found: chunk.data().len(),
+        })?;
+
+        Ok(Self {
+            width: u32::from_be_bytes([data[0], data[1], data[2], data[3]]),
+            height: u32::from_be_bytes([data[4], data[5], data[6], data[7]]),
+            bit_depth: data[8],
+            color_type: data[9],
+            compression: data[10],
+            filter: data[11],
+            interlace: data[12],
+        })
+    }
+}
+
+impl From<IhdrData> for Chunk {
+    fn from(ihdr: IhdrData) -> Self {
+        let mut data = Vec::with_capacity(IhdrData::LENGTH);
+        data.extend_from_slice(&ihdr.width.to_be_bytes());
+        data.extend_from_slice(&ihdr.height.to_be_bytes());
+        data.extend_from_slice(&[
+            ihdr.bit_depth,
+            ihdr.color_type,
+            ihdr.compression,
+            ihdr.filter,
+            ihdr.interlace,
+        ]);
+        Chunk::new(IHDR, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::png::Png;
+    use std::str::FromStr;
+
+    const BASIC_PNG: &[u8] = include_bytes!("../tests/fixtures/basic.png");
+
+    #[test]
+    fn test_ihdr_from_fixture() {
+        let png = Png::try_from(BASIC_PNG).unwrap();
+        let ihdr = IhdrData::try_from(&png.chunks()[0]).unwrap();
+
+        assert_eq!(
+            ihdr,
+            IhdrData {
+                width: 4,
+                height: 2,
+                bit_depth: 8,
+                color_type: 2,
+                compression: 0,
+                filter: 0,
+                interlace: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_ihdr_into_chunk_round_trip() {
+        let png = Png::try_from(BASIC_PNG).unwrap();
+        let ihdr = IhdrData::try_from(&png.chunks()[0]).unwrap();
+
+        assert_eq!(Chunk::from(ihdr), png.chunks()[0]);
+    }
+
+    #[test]
+    fn test_ihdr_wrong_chunk_type() {
+        let chunk = Chunk::new(ChunkType::from_str("IDAT").unwrap(), vec![0; 13]);
+
+        assert!(matches!(
+            IhdrData::try_from(&chunk),
+            Err(PngError::WrongChunkType { expected: IHDR, .. })
+        ));
+    }
+
+    #[test]
+    fn test_ihdr_wrong_length() {
+        let chunk = Chunk::new(IHDR, vec![0; 12]);
+
+        assert!(matches!(
+            IhdrData::try_from(&chunk),
+            Err(PngError::BadLength { expected: 13, found: 12 })
+        ));
+    }
+}
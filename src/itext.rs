@@ -0,0 +1,190 @@
+use std::io::{Read, Write};
+
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+
+use crate::chunk::{decode_latin1, Chunk};
+use crate::chunk_type::ChunkType;
+use crate::error::{PngError, Result};
+use crate::text::encode_keyword;
+
+pub const ITXT: ChunkType = ChunkType::new_unchecked(*b"iTXt");
+
+/// The fields of an `iTXt` chunk, with the text already decompressed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InternationalText {
+    pub keyword: String,
+    pub compressed: bool,
+    pub language: String,
+    pub translated_keyword: String,
+    pub text: String,
+}
+
+impl Chunk {
+    /// Builds an uncompressed `iTXt` chunk. Unlike `tEXt`, the text and
+    /// translated keyword are UTF-8.
+    pub fn itext(
+        keyword: &str,
+        language: &str,
+        translated_keyword: &str,
+        text: &str,
+    ) -> Result<Chunk> {
+        build_itext(keyword, language, translated_keyword, text.as_bytes(), false)
+    }
+
+    /// Like [`Chunk::itext`], but deflates the text with zlib.
+    pub fn itext_compressed(
+        keyword: &str,
+        language: &str,
+        translated_keyword: &str,
+        text: &str,
+    ) -> Result<Chunk> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(text.as_bytes())?;
+        build_itext(keyword, language, translated_keyword, &encoder.finish()?, true)
+    }
+
+    pub fn itext_parts(&self) -> Result<InternationalText> {
+        if *self.chunk_type() != ITXT {
+            return Err(PngError::WrongChunkType {
+                expected: ITXT,
+                found: self.chunk_type().clone(),
+            });
+        }
+
+        let mut rest = self.data();
+        let keyword = split_at_null(&mut rest, "missing null separator after keyword")?;
+        let [compression_flag, compression_method] = rest
+            .get(..2)
+            .and_then(|flags| flags.try_into().ok())
+            .ok_or(malformed("missing compression flag and method"))?;
+        rest = &rest[2..];
+        let compressed = match (compression_flag, compression_method) {
+            (0, _) => false,
+            (1, 0) => true,
+            (1, _) => return Err(malformed("unknown compression method")),
+            _ => return Err(malformed("invalid compression flag")),
+        };
+        let language = split_at_null(&mut rest, "missing null separator after language tag")?;
+        let translated_keyword =
+            split_at_null(&mut rest, "missing null separator after translated keyword")?;
+
+        let text = if compressed {
+            let mut text = Vec::new();
+            ZlibDecoder::new(rest).read_to_end(&mut text).map_err(PngError::Decompress)?;
+            text
+        } else {
+            rest.to_vec()
+        };
+
+        Ok(InternationalText {
+            keyword: decode_latin1(keyword),
+            compressed,
+            language: decode_latin1(language),
+            translated_keyword: String::from_utf8(translated_keyword.to_vec())?,
+            text: String::from_utf8(text)?,
+        })
+    }
+}
+
+fn build_itext(
+    keyword: &str,
+    language: &str,
+    translated_keyword: &str,
+    text: &[u8],
+    compressed: bool,
+) -> Result<Chunk> {
+    if !language.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-') {
+        return Err(malformed("language tag must be ASCII letters, digits, and hyphens"));
+    }
+    if translated_keyword.contains('\0') {
+        return Err(malformed("translated keyword cannot contain null bytes"));
+    }
+
+    let mut data = encode_keyword(keyword)?;
+    data.extend_from_slice(&[0, compressed as u8, 0]);
+    data.extend_from_slice(language.as_bytes());
+    data.push(0);
+    data.extend_from_slice(translated_keyword.as_bytes());
+    data.push(0);
+    data.extend_from_slice(text);
+
+    Chunk::try_new(ITXT, data)
+}
+
+fn split_at_null<'a>(bytes: &mut &'a [u8], reason: &'static str) -> Result<&'a [u8]> {
+    let separator = bytes.iter().position(|&b| b == 0).ok_or(malformed(reason))?;
+    let field = &bytes[..separator];
+    *bytes = &bytes[separator + 1..];
+    Ok(field)
+}
+
+fn malformed(reason: &'static str) -> PngError {
+    PngError::MalformedChunk { chunk_type: ITXT, reason }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::TEXT;
+
+    #[test]
+    fn test_itext_round_trip() {
+        let chunk = Chunk::itext("Title", "en", "Title", "A small picture").unwrap();
+
+        assert_eq!(chunk.chunk_type().to_string(), "iTXt");
+        assert_eq!(chunk.data(), b"Title\0\0\0en\0Title\0A small picture");
+        assert_eq!(
+            chunk.itext_parts().unwrap(),
+            InternationalText {
+                keyword: "Title".to_string(),
+                compressed: false,
+                language: "en".to_string(),
+                translated_keyword: "Title".to_string(),
+                text: "A small picture".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_itext_compressed_round_trip() {
+        let text = "Ein kleines Bild, 小さな絵 ".repeat(20);
+        let chunk = Chunk::itext_compressed("Title", "de-DE", "Überschrift", &text).unwrap();
+
+        assert!(chunk.data().len() < text.len());
+        let parts = chunk.itext_parts().unwrap();
+        assert!(parts.compressed);
+        assert_eq!(parts.language, "de-DE");
+        assert_eq!(parts.translated_keyword, "Überschrift");
+        assert_eq!(parts.text, text);
+    }
+
+    #[test]
+    fn test_itext_rejects_bad_fields() {
+        assert!(matches!(Chunk::itext("", "en", "", "text"), Err(PngError::InvalidKeyword(_))));
+        assert!(matches!(
+            Chunk::itext("Title", "e n", "", "text"),
+            Err(PngError::MalformedChunk { .. })
+        ));
+        assert!(matches!(
+            Chunk::itext("Title", "en", "a\0b", "text"),
+            Err(PngError::MalformedChunk { .. })
+        ));
+    }
+
+    #[test]
+    fn test_itext_parts_errors() {
+        let chunk = Chunk::new(ITXT, b"Title\0\x02\0en\0\0text".to_vec());
+        assert!(matches!(chunk.itext_parts(), Err(PngError::MalformedChunk { .. })));
+
+        let chunk = Chunk::new(ITXT, b"Title\0\0\0en".to_vec());
+        assert!(matches!(chunk.itext_parts(), Err(PngError::MalformedChunk { .. })));
+
+        let chunk = Chunk::new(ITXT, b"Title\0\x01\0en\0\0not zlib".to_vec());
+        assert!(matches!(chunk.itext_parts(), Err(PngError::Decompress(_))));
+
+        let chunk = Chunk::new(TEXT, b"Title\0text".to_vec());
+        assert!(matches!(chunk.itext_parts(), Err(PngError::WrongChunkType { .. })));
+    }
+}
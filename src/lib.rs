@@ -0,0 +1,33 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod builder;
+pub mod chunk;
+#[cfg(feature = "std")]
+pub mod chunk_reader;
+pub mod chunk_ref;
+pub mod chunk_type;
+pub mod diff;
+pub mod error;
+pub mod gama;
+pub mod ihdr;
+#[cfg(feature = "std")]
+pub mod itext;
+pub mod plte;
+pub mod png;
+#[cfg(feature = "std")]
+pub mod png_index;
+pub mod png_ref;
+#[cfg(feature = "serde")]
+mod serde_impl;
+pub mod signature;
+pub mod text;
+pub mod time;
+pub mod trns;
+#[cfg(feature = "std")]
+pub mod ztxt;
+
+pub use chunk::png_crc32;
+pub use error::{PngError as Error, Result};
+pub use signature::is_png;
@@ -1,12 +1,22 @@
-// mod args;
-mod chunk;
-mod chunk_type; 
-// mod commands;
-// mod png;
+mod args;
+mod commands;
 
-pub type Error = Box<dyn std::error::Error>;
-pub type Result<T> = std::result::Result<T, Error>;
+use clap::Parser;
 
-fn main() -> Result<()> {
-    todo!()
+use crate::args::{Cli, Command};
+
+fn main() -> anyhow::Result<()> {
+    let cli = Cli::parse();
+
+    match &cli.command {
+        Command::Encode(args) => commands::encode(args),
+        Command::Decode(args) => commands::decode(args),
+        Command::Remove(args) => commands::remove(args),
+        Command::Print(args) => commands::print(args),
+        Command::Extract(args) => commands::extract(args),
+        Command::List(args) => commands::list(args),
+        Command::Verify(args) => commands::verify(args),
+        Command::Diff(args) => commands::diff(args),
+        Command::Sanitize(args) => commands::sanitize(args),
+    }
 }
@@ -0,0 +1,77 @@
+use alloc::vec::Vec;
+
+use crate::chunk::Chunk;
+use crate::chunk_type::ChunkType;
+use crate::error::{PngError, Result};
+use crate::png::Png;
+
+pub const PLTE: ChunkType = ChunkType::new_unchecked(*b"PLTE");
+
+impl Png {
+    /// The palette entries from the `PLTE` chunk, or `None` if there is no
+    /// `PLTE` chunk or it is malformed. See [`Png::try_palette`] to tell
+    /// those apart.
+    pub fn palette(&self) -> Option<Vec<[u8; 3]>> {
+        self.try_palette().ok().flatten()
+    }
+
+    pub fn try_palette(&self) -> Result<Option<Vec<[u8; 3]>>> {
+        let Some(chunk) = self.iter().find(|chunk| *chunk.chunk_type() == PLTE) else {
+            return Ok(None);
+        };
+        Ok(Some(palette_from_chunk(chunk)?))
+    }
+}
+
+fn palette_from_chunk(chunk: &Chunk) -> Result<Vec<[u8; 3]>> {
+    if !chunk.data().len().is_multiple_of(3) {
+        return Err(PngError::MalformedChunk {
+            chunk_type: PLTE,
+            reason: "length must be a multiple of 3",
+        });
+    }
+
+    Ok(chunk
+        .data()
+        .chunks_exact(3)
+        .map(|rgb| [rgb[0], rgb[1], rgb[2]])
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASIC_PNG: &[u8] = include_bytes!("../tests/fixtures/basic.png");
+    const INDEXED_PNG: &[u8] = include_bytes!("../tests/fixtures/indexed.png");
+
+    #[test]
+    fn test_palette_from_fixture() {
+        let png = Png::try_from(INDEXED_PNG).unwrap();
+        let palette = png.palette().unwrap();
+
+        assert_eq!(palette.len(), 3);
+        assert_eq!(palette[0], [255, 0, 0]);
+        assert_eq!(palette, png.try_palette().unwrap().unwrap());
+    }
+
+    #[test]
+    fn test_palette_absent() {
+        let png = Png::try_from(BASIC_PNG).unwrap();
+
+        assert_eq!(png.palette(), None);
+        assert_eq!(png.try_palette().unwrap(), None);
+    }
+
+    #[test]
+    fn test_palette_wrong_length() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.insert_chunk(1, Chunk::new(PLTE, vec![0; 4])).unwrap();
+
+        assert_eq!(png.palette(), None);
+        assert!(matches!(
+            png.try_palette(),
+            Err(PngError::MalformedChunk { chunk_type: PLTE, .. })
+        ));
+    }
+}
@@ -0,0 +1,1329 @@
+use alloc::format;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::{Read, Write};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use crate::chunk::Chunk;
+use crate::chunk_ref::ChunkRef;
+use crate::chunk_type::ChunkType;
+use crate::error::{ParseWarning, PngError, Result};
+use crate::ihdr::{IhdrData, IHDR};
+use crate::signature::PngSignature;
+
+pub const IDAT: ChunkType = ChunkType::new_unchecked(*b"IDAT");
+pub const IEND: ChunkType = ChunkType::new_unchecked(*b"IEND");
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Png {
+    chunks: Vec<Chunk>,
+}
+
+impl Png {
+    pub const STANDARD_HEADER: [u8; 8] = PngSignature::BYTES;
+
+    /// A conservative default for [`Png::remaining_text_capacity`]: many
+    /// viewers and tools get slow or refuse files with more private chunk
+    /// data than this.
+    pub const DEFAULT_PAYLOAD_SOFT_LIMIT: usize = 1 << 20;
+
+    /// The most data a single chunk can hold under the PNG spec.
+    pub const fn max_chunk_payload() -> usize {
+        Chunk::MAX_LENGTH as usize
+    }
+
+    /// How many more bytes of hidden data fit under `soft_limit`, after
+    /// subtracting the data of the private chunks already present. Standard
+    /// ancillary chunks such as `gAMA` or `iCCP` don't count.
+    pub fn remaining_text_capacity(&self, soft_limit: usize) -> usize {
+        let used: usize = self
+            .chunks
+            .iter()
+            .filter(|chunk| chunk.chunk_type().is_private())
+            .map(|chunk| chunk.length() as usize)
+            .sum();
+        soft_limit.saturating_sub(used)
+    }
+
+    pub fn from_chunks(chunks: Vec<Chunk>) -> Png {
+        Self { chunks }
+    }
+
+    pub fn chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Chunk> {
+        self.chunks.iter()
+    }
+
+    #[cfg(feature = "std")]
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Png> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path).map_err(|source| PngError::PathIo {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Png::try_from(bytes.as_slice())
+    }
+
+    #[cfg(feature = "std")]
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.as_bytes()).map_err(|source| PngError::PathIo {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Maps the file at `path` into memory and parses it, avoiding an
+    /// up-front copy of the whole file. Chunk data is still copied.
+    #[cfg(feature = "mmap")]
+    pub fn from_path_mmap(path: impl AsRef<Path>) -> Result<Png> {
+        let file = std::fs::File::open(path)?;
+        // SAFETY: the map is only read while parsing and dropped before
+        // returning, so it does not outlive this call. Concurrent
+        // modification of the file by another process is the caller's risk,
+        // as with any memory-mapped I/O.
+        let map = unsafe { memmap2::Mmap::map(&file)? };
+        Png::try_from(&map[..])
+    }
+
+    #[cfg(feature = "std")]
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Png> {
+        PngSignature::read_from(&mut reader)?;
+
+        let mut chunks = Vec::new();
+        while let Some(chunk) = Chunk::read_from(&mut reader).map_err(|e| e.in_chunk(chunks.len()))? {
+            chunks.push(chunk);
+        }
+
+        Ok(Self { chunks })
+    }
+
+    /// Parses like `try_from` but skips CRC verification, for trusted input.
+    /// Chunks keep whatever CRC was stored, so a corrupt one can be spotted
+    /// later with [`Png::verify_crcs`].
+    pub fn from_bytes_unchecked(bytes: &[u8]) -> Result<Png> {
+        parse_chunks(bytes, ChunkRef::parse_unverified)
+    }
+
+    /// Parses as much of `bytes` as possible, recording anomalies that
+    /// [`Png::try_from`] would reject (or ignore) as warnings. Parsing stops
+    /// at the first chunk that cannot be read at all.
+    pub fn from_bytes_lenient(bytes: &[u8]) -> (Png, Vec<ParseWarning>) {
+        let mut warnings = Vec::new();
+        if PngSignature::verify(bytes).is_err() {
+            warnings.push(ParseWarning::BadSignature);
+        }
+        let mut rest = bytes.get(PngSignature::BYTES.len()..).unwrap_or_default();
+
+        let mut chunks: Vec<Chunk> = Vec::new();
+        while !rest.is_empty() {
+            if chunks.last().is_some_and(|chunk| has_type(chunk, "IEND")) {
+                warnings.push(ParseWarning::DataAfterIend(rest.len()));
+                break;
+            }
+
+            let Ok(raw) = ChunkRef::parse_unverified(rest) else {
+                warnings.push(ParseWarning::UnreadableData {
+                    offset: bytes.len() - rest.len(),
+                    length: rest.len(),
+                });
+                break;
+            };
+            rest = &rest[12 + raw.data().len()..];
+            let chunk_type = raw.chunk_type().clone();
+
+            if !chunk_type.is_reserved_bit_valid() {
+                warnings.push(ParseWarning::ReservedBitSet(chunk_type.clone()));
+            }
+            if chunk_type.is_critical() && !KNOWN_CRITICAL.contains(&&chunk_type.bytes()) {
+                warnings.push(ParseWarning::UnknownCriticalChunk(chunk_type.clone()));
+            }
+            // Keep the stored CRC, as `from_bytes_unchecked` does, so writing
+            // the file back out doesn't quietly repair it.
+            let expected = Chunk::calculate_crc(&chunk_type, raw.data());
+            if expected != raw.crc() {
+                warnings.push(ParseWarning::CrcMismatch {
+                    chunk_type,
+                    expected,
+                    actual: raw.crc(),
+                });
+            }
+            chunks.push(Chunk::from(raw));
+        }
+
+        (Self { chunks }, warnings)
+    }
+
+    /// Adds `chunk` just before a trailing `IEND`, or at the end if there is
+    /// none. Every other chunk keeps its position and bytes.
+    pub fn append_chunk(&mut self, chunk: Chunk) {
+        let ends_with_iend = self
+            .chunks
+            .last()
+            .is_some_and(|last| last.chunk_type().bytes() == *b"IEND");
+
+        if ends_with_iend {
+            self.chunks.insert(self.chunks.len() - 1, chunk);
+        } else {
+            self.chunks.push(chunk);
+        }
+    }
+
+    /// Replaces the first chunk with the same type as `chunk`, or appends it
+    /// like [`Png::append_chunk`] if there is none.
+    pub fn set_or_append_chunk(&mut self, chunk: Chunk) {
+        match self
+            .chunks
+            .iter_mut()
+            .find(|existing| existing.chunk_type() == chunk.chunk_type())
+        {
+            Some(existing) => *existing = chunk,
+            None => self.append_chunk(chunk),
+        }
+    }
+
+    /// Inserts `chunk` at `index`, refusing positions before a leading `IHDR`
+    /// or after a trailing `IEND`.
+    pub fn insert_chunk(&mut self, index: usize, chunk: Chunk) -> Result<()> {
+        let starts_with_ihdr = self.chunks.first().is_some_and(|c| has_type(c, "IHDR"));
+        let ends_with_iend = self.chunks.last().is_some_and(|c| has_type(c, "IEND"));
+
+        let reason = if index > self.chunks.len() {
+            Some("index is past the end")
+        } else if starts_with_ihdr && index == 0 {
+            Some("chunks cannot precede IHDR")
+        } else if ends_with_iend && index == self.chunks.len() {
+            Some("chunks cannot follow IEND")
+        } else {
+            None
+        };
+        if let Some(reason) = reason {
+            return Err(PngError::InvalidPosition { index, reason });
+        }
+
+        self.chunks.insert(index, chunk);
+        Ok(())
+    }
+
+    /// Inserts `chunk` right after the last chunk of type `after`, with the
+    /// same placement rules as [`Png::insert_chunk`].
+    pub fn insert_after_type(&mut self, after: &str, chunk: Chunk) -> Result<()> {
+        let index = self
+            .chunks
+            .iter()
+            .rposition(|c| has_type(c, after))
+            .ok_or_else(|| PngError::ChunkNotFound(after.to_string()))?;
+        self.insert_chunk(index + 1, chunk)
+    }
+
+    /// Replaces the data of the chunk at `index`, refreshing its CRC.
+    pub fn replace_chunk_data(&mut self, index: usize, data: Vec<u8>) -> Result<()> {
+        let len = self.chunks.len();
+        let chunk = self
+            .chunks
+            .get_mut(index)
+            .ok_or(PngError::IndexOutOfBounds { index, len })?;
+        chunk.set_data(data)
+    }
+
+    pub fn remove_first_chunk(&mut self, chunk_type: &str) -> Result<Chunk> {
+        let index = self
+            .chunks
+            .iter()
+            .position(|chunk| has_type(chunk, chunk_type))
+            .ok_or_else(|| PngError::ChunkNotFound(chunk_type.to_string()))?;
+
+        Ok(self.chunks.remove(index))
+    }
+
+    pub fn remove_all_chunks(&mut self, chunk_type: &str) -> Vec<Chunk> {
+        let (removed, kept) = core::mem::take(&mut self.chunks)
+            .into_iter()
+            .partition(|chunk| has_type(chunk, chunk_type));
+        self.chunks = kept;
+        removed
+    }
+
+    /// Removes every ancillary chunk, returning how many were dropped.
+    pub fn strip_ancillary(&mut self) -> usize {
+        let before = self.chunks.len();
+        self.retain_chunks(|chunk| chunk.chunk_type().is_critical());
+        before - self.chunks.len()
+    }
+
+    /// Keeps only the chunks for which `f` returns true, like `Vec::retain`.
+    /// Nothing is kept automatically: a filter that drops `IHDR` or `IEND`
+    /// leaves a PNG that fails [`Png::validate_structure`].
+    pub fn retain_chunks<F: FnMut(&Chunk) -> bool>(&mut self, f: F) {
+        self.chunks.retain(f);
+    }
+
+    /// Reorders chunks to follow the spec: `IHDR` first, then the color
+    /// space chunks, `PLTE`, the chunks that depend on the palette, a
+    /// contiguous run of `IDAT`s and finally `IEND`. Other chunks stay on
+    /// the side of the first `IDAT` they started on, in their original order.
+    pub fn canonicalize_order(&mut self) {
+        let first_idat = self.chunks.iter().position(|chunk| has_type(chunk, "IDAT"));
+        let mut ranked: Vec<(u8, Chunk)> = self
+            .chunks
+            .drain(..)
+            .enumerate()
+            .map(|(index, chunk)| {
+                let before_idat = first_idat.is_none_or(|first| index < first);
+                (order_rank(&chunk, before_idat), chunk)
+            })
+            .collect();
+        ranked.sort_by_key(|(rank, _)| *rank);
+        self.chunks = ranked.into_iter().map(|(_, chunk)| chunk).collect();
+    }
+
+    pub fn recompute_all_crcs(&mut self) {
+        self.chunks.iter_mut().for_each(Chunk::recompute_crc);
+    }
+
+    /// Recomputes every chunk's CRC and reports the first that doesn't
+    /// match the stored value.
+    pub fn verify_crcs(&self) -> Result<()> {
+        match self.chunks.iter().position(|chunk| !chunk.is_crc_valid()) {
+            Some(index) => Err(self.crc_mismatch(index)),
+            None => Ok(()),
+        }
+    }
+
+    /// Like [`Png::verify_crcs`], but spreads the work across rayon's
+    /// thread pool.
+    #[cfg(feature = "rayon")]
+    pub fn verify_crcs_parallel(&self) -> Result<()> {
+        use rayon::prelude::*;
+
+        match self.chunks.par_iter().position_first(|chunk| !chunk.is_crc_valid()) {
+            Some(index) => Err(self.crc_mismatch(index)),
+            None => Ok(()),
+        }
+    }
+
+    fn crc_mismatch(&self, index: usize) -> PngError {
+        let chunk = &self.chunks[index];
+        PngError::ChunkCrcMismatch {
+            index,
+            expected: Chunk::calculate_crc(chunk.chunk_type(), chunk.data()),
+            actual: chunk.crc(),
+        }
+    }
+
+    pub fn chunk_by_type(&self, chunk_type: &str) -> Option<&Chunk> {
+        self.chunks
+            .iter()
+            .find(|chunk| has_type(chunk, chunk_type))
+    }
+
+    pub fn chunks_by_type(&self, chunk_type: &str) -> Vec<&Chunk> {
+        self.find_chunks(|chunk| has_type(chunk, chunk_type))
+    }
+
+    pub fn find_chunks<F: Fn(&Chunk) -> bool>(&self, pred: F) -> Vec<&Chunk> {
+        self.chunks.iter().filter(|chunk| pred(chunk)).collect()
+    }
+
+    /// The zlib stream formed by concatenating every `IDAT` chunk in order.
+    pub fn idat_data(&self) -> Vec<u8> {
+        self.chunks_by_type("IDAT")
+            .into_iter()
+            .flat_map(|chunk| chunk.data().iter().copied())
+            .collect()
+    }
+
+    pub fn dimensions(&self) -> Option<(u32, u32)> {
+        let ihdr = self.ihdr().ok()?;
+        Some((ihdr.width, ihdr.height))
+    }
+
+    pub fn width(&self) -> Result<u32> {
+        Ok(self.ihdr()?.width)
+    }
+
+    pub fn height(&self) -> Result<u32> {
+        Ok(self.ihdr()?.height)
+    }
+
+    fn ihdr(&self) -> Result<IhdrData> {
+        let chunk = self
+            .chunk_by_type("IHDR")
+            .ok_or_else(|| PngError::ChunkNotFound("IHDR".to_string()))?;
+        IhdrData::try_from(chunk).map_err(|_| PngError::MalformedChunk {
+            chunk_type: IHDR,
+            reason: "data must be exactly 13 bytes",
+        })
+    }
+
+    /// Checks that the file starts with `IHDR`, ends with `IEND`, has exactly
+    /// one of each, and contains at least one `IDAT`.
+    pub fn validate_structure(&self) -> Result<()> {
+        for chunk_type in ["IHDR", "IEND"] {
+            let count = self.chunks_by_type(chunk_type).len();
+            if count != 1 {
+                return Err(PngError::Structure(format!(
+                    "expected exactly one {chunk_type} chunk, found {count}"
+                )));
+            }
+        }
+        if !self.chunks.first().is_some_and(|chunk| has_type(chunk, "IHDR")) {
+            return Err(PngError::Structure("first chunk must be IHDR".to_string()));
+        }
+        if !self.chunks.last().is_some_and(|chunk| has_type(chunk, "IEND")) {
+            return Err(PngError::Structure("last chunk must be IEND".to_string()));
+        }
+        if self.chunk_by_type("IDAT").is_none() {
+            return Err(PngError::Structure("expected at least one IDAT chunk".to_string()));
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
+    pub fn write_to<W: Write>(&self, mut writer: W) -> Result<()> {
+        writer.write_all(&Self::STANDARD_HEADER)?;
+        for chunk in &self.chunks {
+            chunk.write_to(&mut writer)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// The byte offset at which each chunk starts in the serialized output
+    /// of `as_bytes()`, aligned with `chunks()`. The offsets are computed
+    /// from the current chunks on each call, so they only match an input
+    /// file that was parsed strictly and not modified since.
+    pub fn serialized_offsets(&self) -> Vec<usize> {
+        self.chunks
+            .iter()
+            .scan(Self::STANDARD_HEADER.len(), |offset, chunk| {
+                let start = *offset;
+                *offset += 12 + chunk.length() as usize;
+                Some(start)
+            })
+            .collect()
+    }
+
+    /// The size of `as_bytes()`, computed without serializing anything.
+    pub fn total_byte_size(&self) -> usize {
+        Self::STANDARD_HEADER.len()
+            + self
+                .chunks
+                .iter()
+                .map(|chunk| 12 + chunk.length() as usize)
+                .sum::<usize>()
+    }
+
+    /// What `total_byte_size()` would be after `strip_ancillary()`.
+    pub fn minimal_size(&self) -> usize {
+        Self::STANDARD_HEADER.len()
+            + self
+                .chunks
+                .iter()
+                .filter(|chunk| chunk.chunk_type().is_critical())
+                .map(|chunk| 12 + chunk.length() as usize)
+                .sum::<usize>()
+    }
+
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.total_byte_size());
+        bytes.extend_from_slice(&Self::STANDARD_HEADER);
+        for chunk in &self.chunks {
+            bytes.extend_from_slice(&chunk.as_bytes());
+        }
+        bytes
+    }
+}
+
+impl<'a> IntoIterator for &'a Png {
+    type Item = &'a Chunk;
+    type IntoIter = core::slice::Iter<'a, Chunk>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.chunks.iter()
+    }
+}
+
+fn has_type(chunk: &Chunk, chunk_type: &str) -> bool {
+    chunk.chunk_type() == chunk_type
+}
+
+/// Where a chunk belongs in [`Png::canonicalize_order`]; lower sorts first.
+fn order_rank(chunk: &Chunk, before_idat: bool) -> u8 {
+    match &chunk.chunk_type().bytes() {
+        b"IHDR" => 0,
+        b"cHRM" | b"gAMA" | b"iCCP" | b"sBIT" | b"sRGB" => 1,
+        b"PLTE" => 2,
+        b"tRNS" | b"bKGD" | b"hIST" => 3,
+        b"IDAT" => 5,
+        b"IEND" => 7,
+        _ if before_idat => 4,
+        _ => 6,
+    }
+}
+
+const KNOWN_CRITICAL: [&[u8; 4]; 4] = [b"IHDR", b"PLTE", b"IDAT", b"IEND"];
+
+fn parse_chunks<'a>(
+    bytes: &'a [u8],
+    parse_chunk: fn(&'a [u8]) -> Result<ChunkRef<'a>>,
+) -> Result<Png> {
+    PngSignature::verify(bytes)?;
+
+    let mut chunks = Vec::new();
+    let mut rest = &bytes[PngSignature::BYTES.len()..];
+    while !rest.is_empty() {
+        let chunk = parse_chunk(rest).map_err(|e| e.in_chunk(chunks.len()))?;
+        rest = rest.get(12 + chunk.length() as usize..).unwrap_or_default();
+        chunks.push(Chunk::from(chunk));
+    }
+
+    Ok(Png { chunks })
+}
+
+impl TryFrom<&[u8]> for Png {
+    type Error = PngError;
+
+    /// Parses and CRC-checks every chunk. Errors from a chunk come wrapped in
+    /// [`PngError::ChunkParse`] with its index; match on [`PngError::root`]
+    /// to get the underlying error.
+    fn try_from(bytes: &[u8]) -> Result<Self> {
+        parse_chunks(bytes, ChunkRef::try_from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "std")]
+    use std::io::Cursor;
+    use std::str::FromStr;
+
+    fn chunk_from_strings(chunk_type: &str, data: &str) -> Chunk {
+        let chunk_type = ChunkType::from_str(chunk_type).unwrap();
+        let data: Vec<u8> = data.bytes().collect();
+
+        Chunk::new(chunk_type, data)
+    }
+
+    fn testing_chunks() -> Vec<Chunk> {
+        vec![
+            chunk_from_strings("FrSt", "I am the first chunk"),
+            chunk_from_strings("miDl", "I am another chunk"),
+            chunk_from_strings("LASt", "I am the last chunk"),
+        ]
+    }
+
+    const BASIC_PNG: &[u8] = include_bytes!("../tests/fixtures/basic.png");
+    const GAMMA_PNG: &[u8] = include_bytes!("../tests/fixtures/gamma.png");
+    const SPLIT_IDAT_PNG: &[u8] = include_bytes!("../tests/fixtures/split_idat.png");
+
+    fn testing_png_bytes() -> Vec<u8> {
+        Png::STANDARD_HEADER
+            .iter()
+            .copied()
+            .chain(testing_chunks().iter().flat_map(|chunk| chunk.as_bytes()))
+            .collect()
+    }
+
+    #[test]
+    fn test_from_chunks() {
+        let png = Png::from_chunks(testing_chunks());
+
+        assert_eq!(png.chunks().len(), 3);
+        assert_eq!(png.chunks()[0].chunk_type().to_string(), "FrSt");
+        assert_eq!(png.chunks()[2].chunk_type().to_string(), "LASt");
+    }
+
+    #[test]
+    fn test_valid_from_bytes() {
+        let png = Png::try_from(testing_png_bytes().as_ref()).unwrap();
+
+        assert_eq!(png, Png::from_chunks(testing_chunks()));
+    }
+
+    #[test]
+    fn test_invalid_header() {
+        let mut bytes = testing_png_bytes();
+        bytes[1] = b'Q';
+
+        assert!(matches!(Png::try_from(bytes.as_ref()), Err(PngError::BadSignature)));
+        assert!(matches!(
+            Png::try_from(&Png::STANDARD_HEADER[..4]),
+            Err(PngError::BadSignature)
+        ));
+    }
+
+    #[test]
+    fn test_truncated_chunk() {
+        let bytes = testing_png_bytes();
+        let png = Png::try_from(&bytes[..bytes.len() - 3]);
+
+        assert!(png.is_err());
+    }
+
+    #[test]
+    fn test_as_bytes_round_trip() {
+        let png = Png::from_chunks(testing_chunks());
+        let parsed = Png::try_from(png.as_bytes().as_ref()).unwrap();
+
+        assert_eq!(parsed, png);
+    }
+
+    #[test]
+    fn test_fixture_as_bytes() {
+        let png = Png::try_from(BASIC_PNG).unwrap();
+
+        assert_eq!(png.chunks().len(), 3);
+        assert_eq!(png.as_bytes(), BASIC_PNG);
+    }
+
+    #[test]
+    fn test_append_chunk() {
+        let mut png = Png::from_chunks(testing_chunks());
+        png.append_chunk(chunk_from_strings("TeSt", "Message"));
+
+        let chunk = png.chunks().last().unwrap();
+        assert_eq!(chunk.chunk_type().to_string(), "TeSt");
+        assert_eq!(chunk.data_as_string().unwrap(), "Message");
+    }
+
+    #[test]
+    fn test_append_chunk_before_iend() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.append_chunk(chunk_from_strings("ruSt", "Message"));
+
+        let types: Vec<String> = png
+            .chunks()
+            .iter()
+            .map(|chunk| chunk.chunk_type().to_string())
+            .collect();
+        assert_eq!(types, ["IHDR", "IDAT", "ruSt", "IEND"]);
+    }
+
+    #[test]
+    fn test_append_then_remove_chunk() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.append_chunk(chunk_from_strings("ruSt", "Message"));
+
+        let removed = png.remove_first_chunk("ruSt").unwrap();
+        assert_eq!(removed.data_as_string().unwrap(), "Message");
+        assert_eq!(png.as_bytes(), BASIC_PNG);
+    }
+
+    #[test]
+    fn test_insert_chunk() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.insert_chunk(1, chunk_from_strings("gAMA", "gama")).unwrap();
+        png.insert_chunk(3, chunk_from_strings("tEXt", "text")).unwrap();
+
+        let types: Vec<String> = png
+            .chunks()
+            .iter()
+            .map(|chunk| chunk.chunk_type().to_string())
+            .collect();
+        assert_eq!(types, ["IHDR", "gAMA", "IDAT", "tEXt", "IEND"]);
+    }
+
+    #[test]
+    fn test_insert_chunk_out_of_bounds() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+
+        for index in [0, 3, 4] {
+            let result = png.insert_chunk(index, chunk_from_strings("tEXt", "text"));
+            assert!(matches!(result, Err(PngError::InvalidPosition { index: i, .. }) if i == index));
+        }
+        assert_eq!(png.as_bytes(), BASIC_PNG);
+    }
+
+    #[test]
+    fn test_remove_missing_chunk() {
+        let mut png = Png::from_chunks(testing_chunks());
+
+        assert!(matches!(
+            png.remove_first_chunk("ruSt"),
+            Err(PngError::ChunkNotFound(chunk_type)) if chunk_type == "ruSt"
+        ));
+        assert_eq!(png.chunks().len(), 3);
+    }
+
+    #[test]
+    fn test_remove_all_chunks() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        for text in ["first", "second", "third"] {
+            png.append_chunk(chunk_from_strings("tEXt", text));
+        }
+
+        let removed: Vec<String> = png
+            .remove_all_chunks("tEXt")
+            .iter()
+            .map(|chunk| chunk.data_as_string().unwrap())
+            .collect();
+
+        assert_eq!(removed, ["first", "second", "third"]);
+        assert!(png.chunk_by_type("tEXt").is_none());
+        assert_eq!(png.as_bytes(), BASIC_PNG);
+        assert!(png.remove_all_chunks("tEXt").is_empty());
+    }
+
+    #[test]
+    fn test_chunk_by_type() {
+        let png = Png::from_chunks(testing_chunks());
+        let chunk = png.chunk_by_type("FrSt").unwrap();
+
+        assert_eq!(chunk.chunk_type().to_string(), "FrSt");
+        assert_eq!(chunk.data_as_string().unwrap(), "I am the first chunk");
+    }
+
+    #[test]
+    fn test_chunk_by_missing_type() {
+        let png = Png::from_chunks(testing_chunks());
+
+        assert!(png.chunk_by_type("ruSt").is_none());
+    }
+
+    #[test]
+    fn test_chunk_by_malformed_type() {
+        let png = Png::from_chunks(testing_chunks());
+
+        assert!(png.chunk_by_type("FrS").is_none());
+        assert!(png.chunk_by_type("FrStt").is_none());
+        assert!(png.chunk_by_type("").is_none());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_from_reader() {
+        let png = Png::from_reader(Cursor::new(BASIC_PNG)).unwrap();
+
+        assert_eq!(png, Png::try_from(BASIC_PNG).unwrap());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_from_reader_bad_signature() {
+        let mut bytes = BASIC_PNG.to_vec();
+        bytes[0] = 0;
+        let mut cursor = Cursor::new(bytes);
+
+        let png = Png::from_reader(&mut cursor);
+        assert!(matches!(png, Err(PngError::BadSignature)));
+        assert_eq!(cursor.position(), 8);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_from_reader_truncated() {
+        for len in [4, 10, BASIC_PNG.len() - 5, BASIC_PNG.len() - 1] {
+            let png = Png::from_reader(Cursor::new(&BASIC_PNG[..len]));
+            assert!(png.is_err(), "accepted {len} bytes");
+        }
+    }
+
+    #[test]
+    fn test_chunks_by_type() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.append_chunk(chunk_from_strings("tEXt", "first"));
+        png.append_chunk(chunk_from_strings("ruSt", "not text"));
+        png.append_chunk(chunk_from_strings("tEXt", "second"));
+        png.append_chunk(chunk_from_strings("tEXt", "third"));
+
+        let texts: Vec<String> = png
+            .chunks_by_type("tEXt")
+            .iter()
+            .map(|chunk| chunk.data_as_string().unwrap())
+            .collect();
+
+        assert_eq!(texts, ["first", "second", "third"]);
+        assert!(png.chunks_by_type("zTXt").is_empty());
+    }
+
+    fn structure_error(png: &Png) -> String {
+        match png.validate_structure() {
+            Err(PngError::Structure(rule)) => rule,
+            other => panic!("expected a structure error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_validate_structure() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.validate_structure().unwrap();
+
+        png.append_chunk(chunk_from_strings("tEXt", "still valid"));
+        png.validate_structure().unwrap();
+    }
+
+    #[test]
+    fn test_validate_structure_missing_iend() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.remove_first_chunk("IEND").unwrap();
+
+        assert_eq!(structure_error(&png), "expected exactly one IEND chunk, found 0");
+    }
+
+    #[test]
+    fn test_validate_structure_two_ihdrs() {
+        let mut chunks = Png::try_from(BASIC_PNG).unwrap().chunks().to_vec();
+        chunks.insert(1, chunks[0].clone());
+        let png = Png::from_chunks(chunks);
+
+        assert_eq!(structure_error(&png), "expected exactly one IHDR chunk, found 2");
+    }
+
+    #[test]
+    fn test_validate_structure_order() {
+        let mut chunks = Png::try_from(BASIC_PNG).unwrap().chunks().to_vec();
+        chunks.swap(0, 1);
+        assert_eq!(structure_error(&Png::from_chunks(chunks)), "first chunk must be IHDR");
+
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.chunks.push(chunk_from_strings("tEXt", "after IEND"));
+        assert_eq!(structure_error(&png), "last chunk must be IEND");
+
+        png.chunks.pop();
+        png.remove_first_chunk("IDAT").unwrap();
+        assert_eq!(structure_error(&png), "expected at least one IDAT chunk");
+    }
+
+    #[test]
+    fn test_dimensions() {
+        let png = Png::try_from(BASIC_PNG).unwrap();
+        assert_eq!(png.dimensions(), Some((4, 2)));
+
+        let png = Png::from_chunks(testing_chunks());
+        assert_eq!(png.dimensions(), None);
+    }
+
+    #[test]
+    fn test_iter() {
+        let png = Png::try_from(BASIC_PNG).unwrap();
+
+        assert_eq!(png.iter().count(), png.chunks().len());
+        assert_eq!(png.iter().filter(|c| c.chunk_type().is_ancillary()).count(), 0);
+
+        let mut count = 0;
+        for chunk in &png {
+            assert_eq!(chunk, &png.chunks()[count]);
+            count += 1;
+        }
+        assert_eq!(count, 3);
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_from_path_mmap() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/basic.png");
+        let png = Png::from_path_mmap(path).unwrap();
+
+        let bytes = std::fs::read(path).unwrap();
+        assert_eq!(png, Png::try_from(bytes.as_slice()).unwrap());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_save_and_from_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("saved.png");
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.append_chunk(chunk_from_strings("ruSt", "Message"));
+
+        png.save(&path).unwrap();
+
+        assert_eq!(Png::from_path(&path).unwrap(), png);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_from_path_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing.png");
+
+        let err = Png::from_path(&path).unwrap_err();
+
+        assert!(matches!(err, PngError::PathIo { .. }));
+        assert_eq!(err.to_string(), path.display().to_string());
+        let source = std::error::Error::source(&err).unwrap();
+        assert!(source.downcast_ref::<std::io::Error>().is_some());
+    }
+
+    fn png_with_bad_crc_at(index: usize) -> Png {
+        let mut chunks: Vec<Chunk> = (0..100)
+            .map(|i| chunk_from_strings("ruSt", &format!("chunk {i}")))
+            .collect();
+        let chunk = &chunks[index];
+        chunks[index] =
+            Chunk::from_parts(chunk.chunk_type().clone(), chunk.data().to_vec(), 0).unwrap();
+        Png::from_chunks(chunks)
+    }
+
+    #[test]
+    fn test_verify_crcs() {
+        assert!(Png::try_from(BASIC_PNG).unwrap().verify_crcs().is_ok());
+
+        let png = png_with_bad_crc_at(42);
+        assert!(matches!(
+            png.verify_crcs(),
+            Err(PngError::ChunkCrcMismatch { index: 42, actual: 0, .. })
+        ));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_verify_crcs_parallel() {
+        assert!(Png::try_from(BASIC_PNG).unwrap().verify_crcs_parallel().is_ok());
+
+        let png = png_with_bad_crc_at(73);
+        assert!(matches!(
+            png.verify_crcs_parallel(),
+            Err(PngError::ChunkCrcMismatch { index: 73, actual: 0, .. })
+        ));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_write_to() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.append_chunk(chunk_from_strings("ruSt", "Message"));
+
+        let mut written: Vec<u8> = Vec::new();
+        png.write_to(&mut written).unwrap();
+
+        assert_eq!(written, png.as_bytes());
+    }
+
+    #[test]
+    fn test_arbitrary_bytes_never_panic() {
+        let mut seed: u32 = 0xDEAD_BEEF;
+        let mut next = move || {
+            seed ^= seed << 13;
+            seed ^= seed >> 17;
+            seed ^= seed << 5;
+            seed
+        };
+
+        for i in 0..1000 {
+            let len = next() as usize % 256;
+            let mut bytes: Vec<u8> = (0..len).map(|_| next() as u8).collect();
+            match i % 3 {
+                // Pure noise, which almost always fails the signature check.
+                0 => {}
+                // A valid signature followed by noise.
+                1 => {
+                    bytes.splice(0..0, Png::STANDARD_HEADER);
+                }
+                // A real file with a few bytes flipped or cut off.
+                _ => {
+                    bytes = BASIC_PNG.to_vec();
+                    let index = next() as usize % bytes.len();
+                    bytes[index] ^= next() as u8 | 1;
+                    bytes.truncate(next() as usize % (BASIC_PNG.len() + 1));
+                }
+            }
+
+            let _ = Png::try_from(bytes.as_slice());
+            #[cfg(feature = "std")]
+            let _ = Png::from_reader(bytes.as_slice());
+            let _ = Png::from_bytes_lenient(bytes.as_slice());
+        }
+    }
+
+    #[test]
+    fn test_total_byte_size() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        assert_eq!(png.total_byte_size(), BASIC_PNG.len());
+
+        png.append_chunk(chunk_from_strings("ruSt", "Message"));
+        assert_eq!(png.total_byte_size(), png.as_bytes().len());
+        assert_eq!(png.total_byte_size(), BASIC_PNG.len() + 12 + 7);
+
+        assert_eq!(Png::from_chunks(Vec::new()).total_byte_size(), 8);
+    }
+
+    #[test]
+    fn test_header_only() {
+        let png = Png::try_from(&Png::STANDARD_HEADER[..]).unwrap();
+
+        assert!(png.chunks().is_empty());
+    }
+
+    #[test]
+    fn test_lenient_data_after_iend() {
+        let mut bytes = BASIC_PNG.to_vec();
+        bytes.push(0);
+        assert!(Png::try_from(bytes.as_slice()).is_err());
+
+        let (png, warnings) = Png::from_bytes_lenient(&bytes);
+
+        assert_eq!(png, Png::try_from(BASIC_PNG).unwrap());
+        assert_eq!(warnings, [ParseWarning::DataAfterIend(1)]);
+    }
+
+    #[test]
+    fn test_lenient_clean_file() {
+        let (png, warnings) = Png::from_bytes_lenient(BASIC_PNG);
+
+        assert_eq!(png, Png::try_from(BASIC_PNG).unwrap());
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_lenient_chunk_anomalies() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.append_chunk(chunk_from_strings("rust", "reserved bit"));
+        png.append_chunk(chunk_from_strings("RUST", "unknown critical"));
+        let mut bytes = png.as_bytes();
+        // Corrupt the stored CRC of the IHDR chunk.
+        bytes[8 + 12 + 13 - 1] ^= 0xFF;
+
+        let (parsed, warnings) = Png::from_bytes_lenient(&bytes);
+
+        assert_ne!(parsed, png);
+        assert_eq!(parsed.as_bytes(), bytes);
+        assert!(matches!(
+            parsed.verify_crcs(),
+            Err(PngError::ChunkCrcMismatch { index: 0, .. })
+        ));
+        let mut repaired = parsed.clone();
+        repaired.recompute_all_crcs();
+        assert_eq!(repaired, png);
+        assert!(matches!(
+            &warnings[0],
+            ParseWarning::CrcMismatch { chunk_type, .. } if chunk_type == "IHDR"
+        ));
+        assert_eq!(
+            warnings[1..],
+            [
+                ParseWarning::ReservedBitSet(ChunkType::from_str("rust").unwrap()),
+                ParseWarning::UnknownCriticalChunk(ChunkType::from_str("RUST").unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lenient_truncated_chunk() {
+        let bytes = &BASIC_PNG[..BASIC_PNG.len() - 4];
+
+        let (png, warnings) = Png::from_bytes_lenient(bytes);
+
+        assert_eq!(png.chunks().len(), 2);
+        assert_eq!(
+            warnings,
+            [ParseWarning::UnreadableData { offset: BASIC_PNG.len() - 12, length: 8 }]
+        );
+    }
+
+    #[test]
+    fn test_lenient_bad_signature() {
+        let mut bytes = BASIC_PNG.to_vec();
+        bytes[0] = 0;
+
+        let (png, warnings) = Png::from_bytes_lenient(&bytes);
+
+        assert_eq!(png.chunks().len(), 3);
+        assert_eq!(warnings, [ParseWarning::BadSignature]);
+    }
+
+    #[test]
+    fn test_recompute_all_crcs() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.recompute_all_crcs();
+
+        assert_eq!(png.as_bytes(), BASIC_PNG);
+    }
+
+    #[test]
+    fn test_strip_ancillary() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.insert_chunk(1, Chunk::text("Author", "Ferris").unwrap()).unwrap();
+        // 2024-01-01 00:00:00
+        let time = vec![0x07, 0xE8, 1, 1, 0, 0, 0];
+        png.append_chunk(Chunk::new(ChunkType::from_str("tIME").unwrap(), time));
+        png.append_chunk(Chunk::text("Comment", "padding").unwrap());
+
+        assert_eq!(png.strip_ancillary(), 3);
+        assert_eq!(png, Png::try_from(BASIC_PNG).unwrap());
+        assert!(png.validate_structure().is_ok());
+        assert_eq!(png.strip_ancillary(), 0);
+    }
+
+    #[test]
+    fn test_idat_data() {
+        let split = Png::try_from(SPLIT_IDAT_PNG).unwrap();
+        let basic = Png::try_from(BASIC_PNG).unwrap();
+
+        assert_eq!(split.chunks_by_type("IDAT").len(), 3);
+        let data = split.idat_data();
+        assert_eq!(data.len(), 31);
+        // zlib header: deflate, 32K window, best compression.
+        assert_eq!(data[..2], [0x78, 0xDA]);
+        assert_eq!(data, basic.idat_data());
+        assert_eq!(data, basic.chunk_by_type("IDAT").unwrap().data());
+    }
+
+    #[test]
+    fn test_idat_data_empty() {
+        assert!(Png::from_chunks(testing_chunks()).idat_data().is_empty());
+    }
+
+    #[test]
+    fn test_set_or_append_chunk() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+
+        png.set_or_append_chunk(chunk_from_strings("ruSt", "first message"));
+        png.set_or_append_chunk(chunk_from_strings("ruSt", "second message"));
+
+        let chunks = png.chunks_by_type("ruSt");
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].data_as_string().unwrap(), "second message");
+        assert_eq!(png.chunks().len(), 4);
+        assert!(png.validate_structure().is_ok());
+    }
+
+    #[test]
+    fn test_serialized_offsets() {
+        let png = Png::try_from(SPLIT_IDAT_PNG).unwrap();
+        let tagged: Vec<usize> = (4..SPLIT_IDAT_PNG.len() - 4)
+            .filter(|&i| matches!(&SPLIT_IDAT_PNG[i..i + 4], b"IHDR" | b"IDAT" | b"IEND"))
+            .map(|i| i - 4)
+            .collect();
+
+        assert_eq!(tagged.len(), 5);
+        assert_eq!(png.serialized_offsets(), tagged);
+    }
+
+    #[test]
+    fn test_canonicalize_order() {
+        let split = Png::try_from(SPLIT_IDAT_PNG).unwrap();
+        let [ihdr, idat0, idat1, idat2, iend] = split.chunks() else {
+            panic!("expected five chunks");
+        };
+        let gama = Png::try_from(GAMMA_PNG).unwrap().chunk_by_type("gAMA").unwrap().clone();
+        let mut png = Png::from_chunks(vec![
+            chunk_from_strings("ruSt", "before"),
+            idat0.clone(),
+            iend.clone(),
+            chunk_from_strings("teSt", "between"),
+            idat1.clone(),
+            ihdr.clone(),
+            gama,
+            idat2.clone(),
+            chunk_from_strings("laSt", "after"),
+        ]);
+        assert!(png.validate_structure().is_err());
+
+        png.canonicalize_order();
+
+        let types: Vec<String> = png.iter().map(|chunk| chunk.chunk_type().to_string()).collect();
+        assert_eq!(
+            types,
+            ["IHDR", "gAMA", "ruSt", "IDAT", "IDAT", "IDAT", "teSt", "laSt", "IEND"]
+        );
+        assert!(png.validate_structure().is_ok());
+        assert_eq!(png.idat_data(), split.idat_data());
+
+        let mut png = Png::from_chunks(vec![ihdr.clone(), iend.clone(), idat0.clone()]);
+        png.canonicalize_order();
+        let types: Vec<String> = png.iter().map(|chunk| chunk.chunk_type().to_string()).collect();
+        assert_eq!(types, ["IHDR", "IDAT", "IEND"]);
+        assert!(png.validate_structure().is_ok());
+    }
+
+    #[test]
+    fn test_parse_error_names_chunk_index() {
+        let png = Png::try_from(SPLIT_IDAT_PNG).unwrap();
+        let crc_offset = png.serialized_offsets()[2] + 8 + png.chunks()[2].length() as usize;
+        let mut bytes = SPLIT_IDAT_PNG.to_vec();
+        bytes[crc_offset] ^= 0xFF;
+
+        let err = Png::try_from(bytes.as_slice()).unwrap_err();
+        assert_eq!(err.to_string(), "failed parsing chunk at index 2");
+        assert!(matches!(err, PngError::ChunkParse { index: 2, .. }));
+        assert!(matches!(err.root(), PngError::CrcMismatch { .. }));
+    }
+
+    #[test]
+    fn test_from_bytes_unchecked() {
+        let mut bytes = BASIC_PNG.to_vec();
+        // Corrupt the stored CRC of the IHDR chunk.
+        bytes[8 + 12 + 13 - 1] ^= 0xFF;
+
+        assert!(matches!(
+            Png::try_from(bytes.as_slice()).unwrap_err().root(),
+            PngError::CrcMismatch { .. }
+        ));
+        let png = Png::from_bytes_unchecked(&bytes).unwrap();
+        assert_eq!(png.chunks().len(), 3);
+        assert_eq!(png.as_bytes(), bytes);
+        assert!(matches!(
+            png.verify_crcs(),
+            Err(PngError::ChunkCrcMismatch { index: 0, .. })
+        ));
+
+        assert_eq!(
+            Png::from_bytes_unchecked(BASIC_PNG).unwrap(),
+            Png::try_from(BASIC_PNG).unwrap()
+        );
+        assert!(matches!(
+            Png::from_bytes_unchecked(&BASIC_PNG[..BASIC_PNG.len() - 1]).unwrap_err().root(),
+            PngError::BadLength { .. }
+        ));
+    }
+
+    #[test]
+    fn test_payload_capacity() {
+        assert_eq!(Png::max_chunk_payload(), 0x7FFF_FFFF);
+
+        let mut png = Png::try_from(GAMMA_PNG).unwrap();
+        assert!(png.chunk_by_type("gAMA").is_some());
+        assert_eq!(png.remaining_text_capacity(100), 100);
+
+        png.append_chunk(chunk_from_strings("ruSt", "0123456789"));
+        png.append_chunk(chunk_from_strings("ruSt", "01234"));
+        assert_eq!(png.remaining_text_capacity(100), 85);
+        assert_eq!(png.remaining_text_capacity(10), 0);
+        assert_eq!(
+            png.remaining_text_capacity(Png::DEFAULT_PAYLOAD_SOFT_LIMIT),
+            Png::DEFAULT_PAYLOAD_SOFT_LIMIT - 15
+        );
+    }
+
+    #[test]
+    fn test_replace_chunk_data() {
+        let mut png = Png::from_chunks(testing_chunks());
+
+        png.replace_chunk_data(1, b"I am the replaced chunk".to_vec()).unwrap();
+
+        let chunk = &png.chunks()[1];
+        assert_eq!(chunk.chunk_type().to_string(), "miDl");
+        assert_eq!(chunk.data_as_string().unwrap(), "I am the replaced chunk");
+        assert!(chunk.is_crc_valid());
+        assert_eq!(Png::try_from(png.as_bytes().as_ref()).unwrap(), png);
+    }
+
+    #[test]
+    fn test_replace_chunk_data_out_of_range() {
+        let mut png = Png::from_chunks(testing_chunks());
+
+        assert!(matches!(
+            png.replace_chunk_data(3, Vec::new()),
+            Err(PngError::IndexOutOfBounds { index: 3, len: 3 })
+        ));
+        assert_eq!(png, Png::from_chunks(testing_chunks()));
+    }
+
+    #[test]
+    fn test_find_chunks() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.append_chunk(Chunk::new(ChunkType::from_str("ruSt").unwrap(), vec![0; 2048]));
+        png.append_chunk(Chunk::new(ChunkType::from_str("ruSt").unwrap(), vec![0; 16]));
+        png.append_chunk(Chunk::new(ChunkType::from_str("RuSt").unwrap(), vec![0; 4096]));
+
+        let large_ancillary =
+            png.find_chunks(|c| c.chunk_type().is_ancillary() && c.length() > 1024);
+
+        assert_eq!(large_ancillary.len(), 1);
+        assert_eq!(large_ancillary[0].length(), 2048);
+        assert_eq!(png.find_chunks(|_| true).len(), png.chunks().len());
+    }
+
+    #[test]
+    fn test_minimal_size() {
+        let mut png = Png::try_from(GAMMA_PNG).unwrap();
+        png.append_chunk(Chunk::text("Comment", "padding").unwrap());
+
+        // gAMA is 12 + 4 bytes, the "Comment\0padding" tEXt is 12 + 15.
+        assert_eq!(png.minimal_size(), png.total_byte_size() - 16 - 27);
+        assert_eq!(png.minimal_size(), BASIC_PNG.len());
+
+        let total = png.total_byte_size();
+        png.strip_ancillary();
+        assert_eq!(png.total_byte_size(), total - 43);
+    }
+
+    #[test]
+    fn test_retain_critical_chunks() {
+        let mut png = Png::try_from(GAMMA_PNG).unwrap();
+        png.append_chunk(Chunk::text("Comment", "padding").unwrap());
+
+        png.retain_chunks(|chunk| chunk.chunk_type().is_critical());
+
+        assert_eq!(png, Png::try_from(BASIC_PNG).unwrap());
+    }
+
+    #[test]
+    fn test_retain_chunks_by_type() {
+        let mut png = Png::try_from(GAMMA_PNG).unwrap();
+        png.append_chunk(Chunk::text("Comment", "padding").unwrap());
+        let denied = ["tEXt", "IEND"];
+
+        png.retain_chunks(|chunk| !denied.iter().any(|t| chunk.chunk_type() == *t));
+
+        let types: Vec<String> = png.iter().map(|chunk| chunk.chunk_type().to_string()).collect();
+        assert_eq!(types, ["IHDR", "gAMA", "IDAT"]);
+        assert!(png.validate_structure().is_err());
+    }
+
+    #[test]
+    fn test_width_and_height() {
+        let png = Png::try_from(BASIC_PNG).unwrap();
+
+        assert_eq!(png.width().unwrap(), 4);
+        assert_eq!(png.height().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_width_and_height_errors() {
+        let png = Png::from_chunks(testing_chunks());
+        assert_eq!(png.width().unwrap_err().to_string(), "chunk type IHDR not found");
+
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.replace_chunk_data(0, vec![0; 12]).unwrap();
+        assert!(matches!(
+            png.height(),
+            Err(PngError::MalformedChunk { chunk_type: IHDR, .. })
+        ));
+        assert_eq!(
+            png.width().unwrap_err().to_string(),
+            "malformed IHDR chunk: data must be exactly 13 bytes"
+        );
+        assert_eq!(png.dimensions(), None);
+    }
+
+    #[test]
+    fn test_insert_after_type() {
+        let mut png = Png::try_from(SPLIT_IDAT_PNG).unwrap();
+
+        png.insert_after_type("IDAT", chunk_from_strings("ruSt", "after the image"))
+            .unwrap();
+
+        let types: Vec<String> = png.iter().map(|chunk| chunk.chunk_type().to_string()).collect();
+        assert_eq!(types, ["IHDR", "IDAT", "IDAT", "IDAT", "ruSt", "IEND"]);
+    }
+
+    #[test]
+    fn test_insert_after_type_errors() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+
+        assert!(matches!(
+            png.insert_after_type("tEXt", chunk_from_strings("ruSt", "nowhere")),
+            Err(PngError::ChunkNotFound(t)) if t == "tEXt"
+        ));
+        assert!(matches!(
+            png.insert_after_type("IEND", chunk_from_strings("ruSt", "too late")),
+            Err(PngError::InvalidPosition { .. })
+        ));
+        assert_eq!(png, Png::try_from(BASIC_PNG).unwrap());
+    }
+
+    #[test]
+    fn test_png_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Png>();
+    }
+}
@@ -0,0 +1,175 @@
+use std::io::{Read, Seek, SeekFrom};
+
+use crate::chunk::{read_chunk_data, read_chunk_length};
+use crate::chunk_type::ChunkType;
+use crate::error::{PngError, Result};
+use crate::png::IEND;
+use crate::signature::PngSignature;
+
+/// Where one chunk's data lives in the underlying stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexEntry {
+    pub chunk_type: ChunkType,
+    /// Offset of the chunk's first data byte, from the start of the stream.
+    /// The chunk itself, with its length and type, starts 8 bytes earlier.
+    pub offset: u64,
+    pub length: u32,
+}
+
+/// A directory of the chunks in a PNG stream, built without reading any
+/// chunk data. CRCs are not checked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PngIndex {
+    entries: Vec<IndexEntry>,
+}
+
+impl PngIndex {
+    /// Reads the signature and every chunk header in `reader` up to and
+    /// including `IEND`, seeking past the data. Anything after `IEND` is
+    /// ignored. Fails if a chunk's data and CRC would run past the end of
+    /// the stream.
+    pub fn scan<R: Read + Seek>(mut reader: R) -> Result<PngIndex> {
+        let start = reader.stream_position()?;
+        let end = reader.seek(SeekFrom::End(0))?;
+        reader.seek(SeekFrom::Start(start))?;
+        PngSignature::read_from(&mut reader)?;
+
+        let mut entries = Vec::new();
+        while let Some(length) = read_chunk_length(&mut reader)? {
+            let mut chunk_type = [0; 4];
+            reader.read_exact(&mut chunk_type)?;
+            let chunk_type = ChunkType::try_from(chunk_type)?;
+
+            let offset = reader.stream_position()?;
+            let available = end.saturating_sub(offset);
+            if available < u64::from(length) + 4 {
+                return Err(PngError::BadLength {
+                    expected: length as usize + 4,
+                    found: available as usize,
+                });
+            }
+            reader.seek(SeekFrom::Current(i64::from(length) + 4))?;
+            let is_iend = chunk_type == IEND;
+            entries.push(IndexEntry { chunk_type, offset, length });
+            if is_iend {
+                break;
+            }
+        }
+
+        Ok(Self { entries })
+    }
+
+    pub fn entries(&self) -> &[IndexEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Reads the data of chunk `index` from `reader`, which must be the
+    /// stream this index was scanned from.
+    pub fn read_data<R: Read + Seek>(&self, reader: &mut R, index: usize) -> Result<Vec<u8>> {
+        let entry = self.entries.get(index).ok_or(PngError::IndexOutOfBounds {
+            index,
+            len: self.entries.len(),
+        })?;
+
+        reader.seek(SeekFrom::Start(entry.offset))?;
+        read_chunk_data(reader, entry.length)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::png::Png;
+    use std::io::Cursor;
+
+    const SPLIT_IDAT_PNG: &[u8] = include_bytes!("../tests/fixtures/split_idat.png");
+
+    #[test]
+    fn test_scan_then_read_data() {
+        let mut reader = Cursor::new(SPLIT_IDAT_PNG);
+        let index = PngIndex::scan(&mut reader).unwrap();
+        let png = Png::try_from(SPLIT_IDAT_PNG).unwrap();
+
+        assert_eq!(index.len(), png.chunks().len());
+        for (entry, chunk) in index.entries().iter().zip(png.chunks()) {
+            assert_eq!(entry.chunk_type, *chunk.chunk_type());
+            assert_eq!(entry.length, chunk.length());
+        }
+        assert_eq!(index.read_data(&mut reader, 2).unwrap(), png.chunks()[2].data());
+    }
+
+    #[test]
+    fn test_read_data_out_of_bounds() {
+        let mut reader = Cursor::new(SPLIT_IDAT_PNG);
+        let index = PngIndex::scan(&mut reader).unwrap();
+
+        assert!(matches!(
+            index.read_data(&mut reader, index.len()),
+            Err(PngError::IndexOutOfBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn test_scan_rejects_length_past_end() {
+        let mut bytes = PngSignature::BYTES.to_vec();
+        bytes.extend_from_slice(&0x7FFF_FFFFu32.to_be_bytes());
+        bytes.extend_from_slice(b"ruSt");
+
+        assert!(matches!(
+            PngIndex::scan(Cursor::new(&bytes)),
+            Err(PngError::BadLength { found: 0, .. })
+        ));
+
+        let cut = SPLIT_IDAT_PNG.len() - 1;
+        assert!(matches!(
+            PngIndex::scan(Cursor::new(&SPLIT_IDAT_PNG[..cut])),
+            Err(PngError::BadLength { .. })
+        ));
+    }
+
+    #[test]
+    fn test_read_data_from_truncated_stream() {
+        let index = PngIndex::scan(Cursor::new(SPLIT_IDAT_PNG)).unwrap();
+        let mut truncated = Cursor::new(&SPLIT_IDAT_PNG[..index.entries()[1].offset as usize + 1]);
+
+        assert!(matches!(
+            index.read_data(&mut truncated, 1),
+            Err(PngError::BadLength { found: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_scan_stops_at_iend() {
+        let mut bytes = SPLIT_IDAT_PNG.to_vec();
+        bytes.extend_from_slice(&[0xFF; 6]);
+        let index = PngIndex::scan(Cursor::new(&bytes)).unwrap();
+
+        assert_eq!(index.len(), Png::try_from(SPLIT_IDAT_PNG).unwrap().chunks().len());
+        assert_eq!(index.entries().last().unwrap().chunk_type, IEND);
+    }
+
+    #[test]
+    fn test_entry_offset_points_at_data() {
+        let index = PngIndex::scan(Cursor::new(SPLIT_IDAT_PNG)).unwrap();
+        let ihdr = &index.entries()[0];
+
+        assert_eq!(ihdr.offset, 8 + 8);
+        assert_eq!(&SPLIT_IDAT_PNG[ihdr.offset as usize - 4..ihdr.offset as usize], b"IHDR");
+    }
+
+    #[test]
+    fn test_scan_bad_signature() {
+        let mut bytes = SPLIT_IDAT_PNG.to_vec();
+        bytes[0] = 0;
+
+        assert!(matches!(PngIndex::scan(Cursor::new(bytes)), Err(PngError::BadSignature)));
+    }
+}
@@ -0,0 +1,78 @@
+use alloc::vec::Vec;
+
+use crate::chunk_ref::ChunkRef;
+use crate::error::{PngError, Result};
+use crate::signature::PngSignature;
+
+/// A parsed PNG whose chunks borrow their data from the input buffer, for
+/// read-only tools that don't need owned chunks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PngRef<'a> {
+    chunks: Vec<ChunkRef<'a>>,
+}
+
+impl<'a> PngRef<'a> {
+    pub fn chunks(&self) -> &[ChunkRef<'a>] {
+        &self.chunks
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for PngRef<'a> {
+    type Error = PngError;
+
+    fn try_from(bytes: &'a [u8]) -> Result<Self> {
+        PngSignature::verify(bytes)?;
+
+        let mut chunks = Vec::new();
+        let mut rest = &bytes[PngSignature::BYTES.len()..];
+        while !rest.is_empty() {
+            let chunk = ChunkRef::try_from(rest)?;
+            rest = rest.get(12 + chunk.length() as usize..).unwrap_or_default();
+            chunks.push(chunk);
+        }
+
+        Ok(Self { chunks })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::png::Png;
+
+    const BASIC_PNG: &[u8] = include_bytes!("../tests/fixtures/basic.png");
+
+    #[test]
+    fn test_png_ref_matches_png() {
+        let png_ref = PngRef::try_from(BASIC_PNG).unwrap();
+        let png = Png::try_from(BASIC_PNG).unwrap();
+
+        assert_eq!(png_ref.chunks().len(), png.chunks().len());
+        for (chunk_ref, chunk) in png_ref.chunks().iter().zip(png.chunks()) {
+            assert_eq!(chunk_ref.chunk_type(), chunk.chunk_type());
+            assert_eq!(chunk_ref.data(), chunk.data());
+            assert_eq!(chunk_ref.crc(), chunk.crc());
+        }
+    }
+
+    #[test]
+    fn test_png_ref_borrows_buffer() {
+        let png_ref = PngRef::try_from(BASIC_PNG).unwrap();
+        let buffer = BASIC_PNG.as_ptr_range();
+
+        for chunk in png_ref.chunks() {
+            assert!(buffer.contains(&chunk.data().as_ptr()) || chunk.data().is_empty());
+        }
+        // The IDAT payload starts after the signature, the 25-byte IHDR, and
+        // the IDAT's own 8-byte header.
+        assert_eq!(png_ref.chunks()[1].data().as_ptr(), BASIC_PNG[41..].as_ptr());
+    }
+
+    #[test]
+    fn test_png_ref_bad_signature() {
+        assert!(matches!(
+            PngRef::try_from(&BASIC_PNG[1..]),
+            Err(PngError::BadSignature)
+        ));
+    }
+}
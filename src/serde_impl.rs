@@ -0,0 +1,100 @@
+use std::str::FromStr;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::chunk::Chunk;
+use crate::chunk_type::ChunkType;
+
+impl Serialize for ChunkType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ChunkType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        ChunkType::from_str(&s).map_err(D::Error::custom)
+    }
+}
+
+/// The serialized shape of a `Chunk`, with its data as base64.
+#[derive(Serialize, Deserialize)]
+struct ChunkRepr {
+    #[serde(rename = "type")]
+    chunk_type: ChunkType,
+    length: u32,
+    crc: u32,
+    data: String,
+}
+
+impl Serialize for Chunk {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ChunkRepr {
+            chunk_type: self.chunk_type().clone(),
+            length: self.length(),
+            crc: self.crc(),
+            data: BASE64.encode(self.data()),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Chunk {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = ChunkRepr::deserialize(deserializer)?;
+        let data = BASE64.decode(&repr.data).map_err(D::Error::custom)?;
+        if data.len() != repr.length as usize {
+            return Err(D::Error::custom(format!(
+                "length is {} but data is {} bytes",
+                repr.length,
+                data.len()
+            )));
+        }
+
+        Chunk::verified(repr.chunk_type, data, repr.crc).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn testing_chunk() -> Chunk {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        Chunk::new(chunk_type, b"This is where your secret message will be!".to_vec())
+    }
+
+    #[test]
+    fn test_chunk_type_json() {
+        let chunk_type = ChunkType::from_str("RuSt").unwrap();
+        let json = serde_json::to_string(&chunk_type).unwrap();
+
+        assert_eq!(json, r#""RuSt""#);
+        assert_eq!(serde_json::from_str::<ChunkType>(&json).unwrap(), chunk_type);
+        assert!(serde_json::from_str::<ChunkType>(r#""Ru1t""#).is_err());
+    }
+
+    #[test]
+    fn test_chunk_json_round_trip() {
+        let chunk = testing_chunk();
+        let json = serde_json::to_string(&chunk).unwrap();
+
+        assert!(json.contains(r#""type":"RuSt""#));
+        assert!(json.contains(r#""length":42"#));
+        assert!(json.contains(r#""crc":2882656334"#));
+        assert_eq!(serde_json::from_str::<Chunk>(&json).unwrap(), chunk);
+    }
+
+    #[test]
+    fn test_chunk_json_bad_crc() {
+        let json = serde_json::to_string(&testing_chunk())
+            .unwrap()
+            .replace("2882656334", "2882656333");
+
+        assert!(serde_json::from_str::<Chunk>(&json).is_err());
+    }
+}
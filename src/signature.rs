@@ -0,0 +1,119 @@
+#[cfg(feature = "std")]
+use std::io::{ErrorKind, Read};
+
+use crate::error::{PngError, Result};
+
+/// The 8-byte magic number every PNG file starts with. Only the standard
+/// bytes in [`PngSignature::BYTES`] make a valid value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PngSignature([u8; 8]);
+
+impl PngSignature {
+    pub const BYTES: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+    pub fn bytes(&self) -> [u8; 8] {
+        self.0
+    }
+
+    /// Checks that `bytes` starts with the signature. Anything after the
+    /// first eight bytes is ignored.
+    pub fn verify(bytes: &[u8]) -> Result<PngSignature> {
+        let header: [u8; 8] = bytes
+            .get(..Self::BYTES.len())
+            .and_then(|header| header.try_into().ok())
+            .ok_or(PngError::BadSignature)?;
+        PngSignature::try_from(header)
+    }
+
+    /// Reads the first eight bytes of `reader` and checks them. A stream
+    /// too short to hold a signature is a `BadSignature` too.
+    #[cfg(feature = "std")]
+    pub fn read_from<R: Read>(reader: &mut R) -> Result<PngSignature> {
+        let mut header = [0; 8];
+        reader.read_exact(&mut header).map_err(|e| match e.kind() {
+            ErrorKind::UnexpectedEof => PngError::BadSignature,
+            _ => e.into(),
+        })?;
+        PngSignature::try_from(header)
+    }
+}
+
+impl Default for PngSignature {
+    fn default() -> Self {
+        PngSignature(Self::BYTES)
+    }
+}
+
+impl TryFrom<[u8; 8]> for PngSignature {
+    type Error = PngError;
+
+    fn try_from(bytes: [u8; 8]) -> Result<Self> {
+        if bytes != Self::BYTES {
+            return Err(PngError::BadSignature);
+        }
+        Ok(PngSignature(bytes))
+    }
+}
+
+/// Returns whether `bytes` starts with the PNG signature, without looking
+/// at any chunks.
+pub fn is_png(bytes: &[u8]) -> bool {
+    PngSignature::verify(bytes).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_signature() {
+        assert_eq!(PngSignature::verify(&PngSignature::BYTES).unwrap(), PngSignature::default());
+        assert!(PngSignature::verify(b"\x89PNG\r\n\x1a\n and then chunks").is_ok());
+    }
+
+    #[test]
+    fn test_verify_truncated_signature() {
+        for len in 0..PngSignature::BYTES.len() {
+            assert!(matches!(
+                PngSignature::verify(&PngSignature::BYTES[..len]),
+                Err(PngError::BadSignature)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_is_png() {
+        assert!(is_png(include_bytes!("../tests/fixtures/basic.png")));
+        assert!(!is_png(&[0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10, b'J', b'F', b'I', b'F']));
+        assert!(!is_png(&PngSignature::BYTES[..3]));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_read_signature() {
+        let mut reader = &b"\x89PNG\r\n\x1a\n and then chunks"[..];
+        assert_eq!(PngSignature::read_from(&mut reader).unwrap().bytes(), PngSignature::BYTES);
+        assert_eq!(reader, b" and then chunks");
+
+        assert!(matches!(
+            PngSignature::read_from(&mut &PngSignature::BYTES[..5]),
+            Err(PngError::BadSignature)
+        ));
+    }
+
+    #[test]
+    fn test_verify_flipped_byte() {
+        for index in 0..PngSignature::BYTES.len() {
+            let mut bytes = PngSignature::BYTES;
+            bytes[index] ^= 0x01;
+
+            assert!(matches!(PngSignature::verify(&bytes), Err(PngError::BadSignature)));
+        }
+    }
+
+    #[test]
+    fn test_signature_try_from() {
+        assert_eq!(PngSignature::try_from(PngSignature::BYTES).unwrap(), PngSignature::default());
+        assert!(matches!(PngSignature::try_from([0; 8]), Err(PngError::BadSignature)));
+    }
+}
@@ -0,0 +1,164 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::chunk::{decode_latin1, Chunk};
+use crate::chunk_type::ChunkType;
+use crate::error::{PngError, Result};
+#[cfg(feature = "std")]
+use crate::itext::ITXT;
+use crate::png::Png;
+#[cfg(feature = "std")]
+use crate::ztxt::ZTXT;
+
+pub const TEXT: ChunkType = ChunkType::new_unchecked(*b"tEXt");
+
+impl Chunk {
+    /// Builds a `tEXt` chunk holding `keyword`, a null separator, and `text`,
+    /// both encoded as Latin-1 as the PNG spec requires.
+    pub fn text(keyword: &str, text: &str) -> Result<Chunk> {
+        let mut data = encode_keyword(keyword)?;
+        data.push(0);
+        data.extend(encode_latin1(text)?);
+
+        Chunk::try_new(TEXT, data)
+    }
+
+    /// Splits a `tEXt` chunk back into its keyword and text.
+    pub fn text_parts(&self) -> Result<(String, String)> {
+        if *self.chunk_type() != TEXT {
+            return Err(PngError::WrongChunkType {
+                expected: TEXT,
+                found: self.chunk_type().clone(),
+            });
+        }
+
+        let separator = self.data().iter().position(|&b| b == 0).ok_or(PngError::MalformedChunk {
+            chunk_type: TEXT,
+            reason: "missing null separator after keyword",
+        })?;
+        let (keyword, text) = (&self.data()[..separator], &self.data()[separator + 1..]);
+
+        Ok((decode_latin1(keyword), decode_latin1(text)))
+    }
+}
+
+impl Png {
+    /// The keyword and text of every `tEXt`, `zTXt` and `iTXt` chunk, in
+    /// file order. Chunks that fail to decode are skipped.
+    #[cfg(feature = "std")]
+    pub fn text_entries(&self) -> Vec<(String, String)> {
+        self.iter()
+            .filter_map(|chunk| {
+                let chunk_type = chunk.chunk_type();
+                if *chunk_type == TEXT {
+                    chunk.text_parts().ok()
+                } else if *chunk_type == ZTXT {
+                    chunk.ztext_parts().ok()
+                } else if *chunk_type == ITXT {
+                    chunk.itext_parts().ok().map(|itext| (itext.keyword, itext.text))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn encode_keyword(keyword: &str) -> Result<Vec<u8>> {
+    let bytes = encode_latin1(keyword)?;
+    if bytes.is_empty() || bytes.len() > 79 {
+        return Err(PngError::InvalidKeyword(format!(
+            "must be 1-79 bytes, got {}",
+            bytes.len()
+        )));
+    }
+    if let Some(b) = bytes.iter().find(|&&b| !(32..=126).contains(&b) && b < 161) {
+        return Err(PngError::InvalidKeyword(format!("contains non-printable byte {b:#04x}")));
+    }
+    Ok(bytes)
+}
+
+pub(crate) fn encode_latin1(s: &str) -> Result<Vec<u8>> {
+    s.chars()
+        .map(|c| u8::try_from(c).map_err(|_| PngError::InvalidLatin1(c)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_text_round_trip() {
+        let chunk = Chunk::text("Author", "Alice").unwrap();
+
+        assert_eq!(chunk.chunk_type().to_string(), "tEXt");
+        assert_eq!(chunk.data(), b"Author\0Alice");
+        assert_eq!(
+            chunk.text_parts().unwrap(),
+            ("Author".to_string(), "Alice".to_string())
+        );
+    }
+
+    #[test]
+    fn test_text_latin1_round_trip() {
+        let chunk = Chunk::text("Comment", "café").unwrap();
+
+        assert_eq!(chunk.data(), b"Comment\0caf\xE9");
+        assert_eq!(chunk.text_parts().unwrap().1, "café");
+    }
+
+    #[test]
+    fn test_text_rejects_long_keyword() {
+        assert!(Chunk::text(&"k".repeat(79), "ok").is_ok());
+        assert!(matches!(
+            Chunk::text(&"k".repeat(80), "too long"),
+            Err(PngError::InvalidKeyword(_))
+        ));
+    }
+
+    #[test]
+    fn test_text_rejects_invalid_keyword() {
+        assert!(matches!(Chunk::text("", "text"), Err(PngError::InvalidKeyword(_))));
+        assert!(matches!(Chunk::text("a\0b", "text"), Err(PngError::InvalidKeyword(_))));
+        assert!(matches!(Chunk::text("Autor€", "text"), Err(PngError::InvalidLatin1('€'))));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_text_entries_from_fixture() {
+        let png = Png::try_from(&include_bytes!("../tests/fixtures/text.png")[..]).unwrap();
+
+        assert_eq!(
+            png.text_entries(),
+            [
+                ("Author".to_string(), "Ferris".to_string()),
+                ("Comment".to_string(), "Made in a café".to_string()),
+                ("Title".to_string(), "Кот ✓".to_string()),
+            ]
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_text_entries_skips_malformed() {
+        let png = Png::from_chunks(vec![
+            Chunk::new(TEXT, b"no separator".to_vec()),
+            Chunk::text("Author", "Alice").unwrap(),
+            Chunk::new(ZTXT, b"Comment\0\0not zlib".to_vec()),
+        ]);
+
+        assert_eq!(png.text_entries(), [("Author".to_string(), "Alice".to_string())]);
+    }
+
+    #[test]
+    fn test_text_parts_errors() {
+        let chunk = Chunk::new(TEXT, b"no separator".to_vec());
+        assert!(matches!(chunk.text_parts(), Err(PngError::MalformedChunk { .. })));
+
+        let chunk = Chunk::new(ChunkType::from_str("ruSt").unwrap(), b"a\0b".to_vec());
+        assert!(matches!(chunk.text_parts(), Err(PngError::WrongChunkType { .. })));
+    }
+}
@@ -0,0 +1,104 @@
+use alloc::vec::Vec;
+
+use crate::chunk::Chunk;
+use crate::chunk_type::ChunkType;
+use crate::error::{PngError, Result};
+use crate::png::Png;
+
+pub const TIME: ChunkType = ChunkType::new_unchecked(*b"tIME");
+
+/// Year, month, day, hour, minute, second, as stored in `tIME` (UTC).
+pub type ModificationTime = (u16, u8, u8, u8, u8, u8);
+
+impl Chunk {
+    /// Builds a `tIME` chunk. The second may be 60 to allow for leap seconds.
+    pub fn time(
+        year: u16,
+        month: u8,
+        day: u8,
+        hour: u8,
+        minute: u8,
+        second: u8,
+    ) -> Result<Chunk> {
+        validate((year, month, day, hour, minute, second))?;
+
+        let mut data = Vec::with_capacity(7);
+        data.extend_from_slice(&year.to_be_bytes());
+        data.extend_from_slice(&[month, day, hour, minute, second]);
+        Chunk::try_new(TIME, data)
+    }
+}
+
+impl Png {
+    /// The last-modification time from the `tIME` chunk, or `None` if there
+    /// is no `tIME` chunk or it is malformed.
+    pub fn modification_time(&self) -> Option<ModificationTime> {
+        let chunk = self.iter().find(|chunk| *chunk.chunk_type() == TIME)?;
+        let &[y0, y1, month, day, hour, minute, second] = chunk.data() else {
+            return None;
+        };
+        let time = (u16::from_be_bytes([y0, y1]), month, day, hour, minute, second);
+        validate(time).ok().map(|_| time)
+    }
+}
+
+fn validate((_, month, day, hour, minute, second): ModificationTime) -> Result<()> {
+    let checks: [(bool, &'static str); 5] = [
+        ((1..=12).contains(&month), "month must be 1-12"),
+        ((1..=31).contains(&day), "day must be 1-31"),
+        (hour <= 23, "hour must be 0-23"),
+        (minute <= 59, "minute must be 0-59"),
+        (second <= 60, "second must be 0-60"),
+    ];
+    match checks.into_iter().find(|(valid, _)| !valid) {
+        Some((_, reason)) => Err(PngError::MalformedChunk { chunk_type: TIME, reason }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASIC_PNG: &[u8] = include_bytes!("../tests/fixtures/basic.png");
+
+    #[test]
+    fn test_time_round_trip() {
+        let chunk = Chunk::time(2024, 2, 29, 23, 59, 60).unwrap();
+        assert_eq!(chunk.chunk_type().to_string(), "tIME");
+        assert_eq!(chunk.data(), [0x07, 0xE8, 2, 29, 23, 59, 60]);
+
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        assert_eq!(png.modification_time(), None);
+
+        png.append_chunk(chunk);
+        assert_eq!(png.modification_time(), Some((2024, 2, 29, 23, 59, 60)));
+
+        let reparsed = Png::try_from(png.as_bytes().as_slice()).unwrap();
+        assert_eq!(reparsed.modification_time(), Some((2024, 2, 29, 23, 59, 60)));
+    }
+
+    #[test]
+    fn test_time_rejects_out_of_range() {
+        assert!(matches!(
+            Chunk::time(2024, 13, 1, 0, 0, 0),
+            Err(PngError::MalformedChunk { chunk_type: TIME, reason: "month must be 1-12" })
+        ));
+        assert!(Chunk::time(2024, 0, 1, 0, 0, 0).is_err());
+        assert!(Chunk::time(2024, 1, 32, 0, 0, 0).is_err());
+        assert!(Chunk::time(2024, 1, 1, 24, 0, 0).is_err());
+        assert!(Chunk::time(2024, 1, 1, 0, 60, 0).is_err());
+        assert!(Chunk::time(2024, 1, 1, 0, 0, 61).is_err());
+    }
+
+    #[test]
+    fn test_modification_time_malformed() {
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.append_chunk(Chunk::new(TIME, vec![0x07, 0xE8, 13, 1, 0, 0, 0]));
+        assert_eq!(png.modification_time(), None);
+
+        let mut png = Png::try_from(BASIC_PNG).unwrap();
+        png.append_chunk(Chunk::new(TIME, vec![0x07, 0xE8, 1, 1]));
+        assert_eq!(png.modification_time(), None);
+    }
+}
@@ -0,0 +1,39 @@
+use crate::chunk_type::ChunkType;
+use crate::png::Png;
+
+pub const TRNS: ChunkType = ChunkType::new_unchecked(*b"tRNS");
+
+impl Png {
+    /// The raw data of the `tRNS` chunk. How it is laid out depends on the
+    /// image's color type, so interpreting it is left to the caller.
+    pub fn transparency(&self) -> Option<&[u8]> {
+        self.iter()
+            .find(|chunk| *chunk.chunk_type() == TRNS)
+            .map(|chunk| chunk.data())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASIC_PNG: &[u8] = include_bytes!("../tests/fixtures/basic.png");
+    const TRANSPARENT_PNG: &[u8] = include_bytes!("../tests/fixtures/transparent.png");
+
+    #[test]
+    fn test_transparency_from_fixture() {
+        let png = Png::try_from(TRANSPARENT_PNG).unwrap();
+
+        // One alpha value per palette entry.
+        let alpha = png.transparency().unwrap();
+        assert_eq!(alpha.len(), png.palette().unwrap().len());
+        assert_eq!(alpha, [0x00, 0x80, 0xFF]);
+    }
+
+    #[test]
+    fn test_transparency_absent() {
+        let png = Png::try_from(BASIC_PNG).unwrap();
+
+        assert_eq!(png.transparency(), None);
+    }
+}
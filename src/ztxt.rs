@@ -0,0 +1,86 @@
+use std::io::{Read, Write};
+
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+
+use crate::chunk::{decode_latin1, Chunk};
+use crate::chunk_type::ChunkType;
+use crate::error::{PngError, Result};
+use crate::text::{encode_keyword, encode_latin1};
+
+pub const ZTXT: ChunkType = ChunkType::new_unchecked(*b"zTXt");
+
+impl Chunk {
+    /// Builds a `zTXt` chunk: like `tEXt`, but the Latin-1 text is deflated
+    /// with zlib.
+    pub fn ztext(keyword: &str, text: &str) -> Result<Chunk> {
+        let mut data = encode_keyword(keyword)?;
+        data.extend_from_slice(&[0, 0]);
+        let mut encoder = ZlibEncoder::new(data, Compression::default());
+        encoder.write_all(&encode_latin1(text)?)?;
+
+        Chunk::try_new(ZTXT, encoder.finish()?)
+    }
+
+    /// Splits a `zTXt` chunk into its keyword and decompressed text.
+    pub fn ztext_parts(&self) -> Result<(String, String)> {
+        if *self.chunk_type() != ZTXT {
+            return Err(PngError::WrongChunkType {
+                expected: ZTXT,
+                found: self.chunk_type().clone(),
+            });
+        }
+
+        let separator = self
+            .data()
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or(malformed("missing null separator after keyword"))?;
+        let (keyword, rest) = (&self.data()[..separator], &self.data()[separator + 1..]);
+        let compressed = match rest {
+            [0, compressed @ ..] => compressed,
+            [_, ..] => return Err(malformed("unknown compression method")),
+            [] => return Err(malformed("missing compression method")),
+        };
+
+        let mut text = Vec::new();
+        ZlibDecoder::new(compressed).read_to_end(&mut text).map_err(PngError::Decompress)?;
+        Ok((decode_latin1(keyword), decode_latin1(&text)))
+    }
+}
+
+fn malformed(reason: &'static str) -> PngError {
+    PngError::MalformedChunk { chunk_type: ZTXT, reason }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::TEXT;
+
+    #[test]
+    fn test_ztext_round_trip() {
+        let chunk = Chunk::ztext("Comment", "café ".repeat(20).trim_end()).unwrap();
+
+        assert_eq!(chunk.chunk_type().to_string(), "zTXt");
+        assert!(chunk.data().starts_with(b"Comment\0\0"));
+        assert!(chunk.data().len() < 100);
+        assert_eq!(
+            chunk.ztext_parts().unwrap(),
+            ("Comment".to_string(), "café ".repeat(20).trim_end().to_string())
+        );
+    }
+
+    #[test]
+    fn test_ztext_parts_errors() {
+        let chunk = Chunk::new(ZTXT, b"Comment\0\x01\x78\x9c".to_vec());
+        assert!(matches!(chunk.ztext_parts(), Err(PngError::MalformedChunk { .. })));
+
+        let chunk = Chunk::new(ZTXT, b"Comment\0\0not zlib".to_vec());
+        assert!(matches!(chunk.ztext_parts(), Err(PngError::Decompress(_))));
+
+        let chunk = Chunk::new(TEXT, b"Comment\0\0".to_vec());
+        assert!(matches!(chunk.ztext_parts(), Err(PngError::WrongChunkType { .. })));
+    }
+}
@@ -0,0 +1,23 @@
+use std::str::FromStr;
+
+use pngme::chunk_type::ChunkType;
+
+#[test]
+fn test_properties_through_public_api() {
+    let chunk_type = ChunkType::from_str("RuSt").unwrap();
+    assert!(chunk_type.is_valid());
+    assert!(chunk_type.is_critical());
+    assert!(!chunk_type.is_public());
+    assert!(chunk_type.is_reserved_bit_valid());
+    assert!(chunk_type.is_safe_to_copy());
+}
+
+#[test]
+fn test_unknown_ancillary_chunk_is_safe_to_copy() {
+    let chunk_type = ChunkType::from_str("tEXt").unwrap();
+    assert!(!chunk_type.is_critical());
+    assert!(chunk_type.is_safe_to_copy());
+
+    let chunk_type = ChunkType::from_str("tEXT").unwrap();
+    assert!(!chunk_type.is_safe_to_copy());
+}
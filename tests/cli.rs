@@ -0,0 +1,523 @@
+#![cfg(feature = "cli")]
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use assert_cmd::Command;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use pngme::png::Png;
+use tempfile::TempDir;
+
+const BASIC_PNG: &[u8] = include_bytes!("fixtures/basic.png");
+
+fn pngme() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_pngme"))
+}
+
+fn scratch_png(dir: &TempDir) -> PathBuf {
+    let path = dir.path().join("image.png");
+    fs::write(&path, BASIC_PNG).unwrap();
+    path
+}
+
+fn stdout_of(cmd: &mut Command) -> String {
+    let assert = cmd.assert().success();
+    String::from_utf8(assert.get_output().stdout.clone()).unwrap()
+}
+
+fn encode(path: &Path, chunk_type: &str, message: &str) {
+    pngme()
+        .arg("encode")
+        .arg(path)
+        .args([chunk_type, message])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_encode_then_decode() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+
+    encode(&path, "ruSt", "This is a secret");
+    let decoded = stdout_of(pngme().arg("decode").arg(&path).arg("ruSt"));
+
+    assert_eq!(decoded.trim_end(), "This is a secret");
+}
+
+#[test]
+fn test_encode_to_output_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+    let output = dir.path().join("out.png");
+
+    pngme()
+        .arg("encode")
+        .arg(&path)
+        .args(["ruSt", "This is a secret", "--output"])
+        .arg(&output)
+        .assert()
+        .success();
+
+    assert_eq!(fs::read(&path).unwrap(), BASIC_PNG);
+    let decoded = stdout_of(pngme().arg("decode").arg(&output).arg("ruSt"));
+    assert_eq!(decoded.trim_end(), "This is a secret");
+}
+
+#[test]
+fn test_remove_restores_original() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+
+    encode(&path, "ruSt", "This is a secret");
+    pngme().arg("remove").arg(&path).arg("ruSt").assert().success();
+
+    assert_eq!(fs::read(&path).unwrap(), BASIC_PNG);
+    pngme().arg("decode").arg(&path).arg("ruSt").assert().failure();
+}
+
+#[test]
+fn test_print_lists_chunks() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+
+    let printed = stdout_of(pngme().arg("print").arg(&path));
+    let lines: Vec<&str> = printed.lines().collect();
+
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].contains("type: IHDR, length: 13"));
+    assert!(lines[1].contains("type: IDAT"));
+    assert!(lines[2].contains("type: IEND, length: 0"));
+}
+
+#[test]
+fn test_encode_compressed_then_decode() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+    let message = "squeeze me ".repeat(1000);
+
+    pngme()
+        .arg("encode")
+        .arg(&path)
+        .args(["ruSt", &message, "--compress"])
+        .assert()
+        .success();
+    let decoded = stdout_of(pngme().arg("decode").arg(&path).arg("ruSt"));
+
+    assert_eq!(decoded.trim_end(), message.trim_end());
+    assert!(fs::read(&path).unwrap().len() < BASIC_PNG.len() + message.len());
+}
+
+#[test]
+fn test_encode_dry_run_leaves_file_unchanged() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+
+    let output = stdout_of(
+        pngme()
+            .arg("encode")
+            .arg(&path)
+            .args(["ruSt", "This is a secret", "--dry-run"]),
+    );
+
+    assert_eq!(fs::read(&path).unwrap(), BASIC_PNG);
+    assert!(output.contains("type: ruSt, length: 16"));
+    assert!(output.contains(&format!("New file size: {} bytes", BASIC_PNG.len() + 12 + 16)));
+}
+
+#[test]
+fn test_encode_file_then_decode_to_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+    let secret = dir.path().join("secret.bin");
+    let restored = dir.path().join("restored.bin");
+    let payload: Vec<u8> = (0..=255).rev().collect();
+    fs::write(&secret, &payload).unwrap();
+
+    pngme()
+        .arg("encode")
+        .arg(&path)
+        .arg("ruSt")
+        .arg("--file")
+        .arg(&secret)
+        .assert()
+        .success();
+    pngme()
+        .arg("decode")
+        .arg(&path)
+        .arg("ruSt")
+        .arg("--out")
+        .arg(&restored)
+        .assert()
+        .success();
+
+    assert_eq!(fs::read(&restored).unwrap(), payload);
+}
+
+#[test]
+fn test_decode_out_keeps_zlib_payload_verbatim() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+    let secret = dir.path().join("secret.bin");
+    let restored = dir.path().join("restored.bin");
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(b"already compressed").unwrap();
+    let mut payload = encoder.finish().unwrap();
+    payload.extend_from_slice(b"TRAILER");
+    fs::write(&secret, &payload).unwrap();
+
+    pngme()
+        .arg("encode")
+        .arg(&path)
+        .arg("ruSt")
+        .arg("--file")
+        .arg(&secret)
+        .assert()
+        .success();
+    pngme()
+        .arg("decode")
+        .arg(&path)
+        .arg("ruSt")
+        .arg("--out")
+        .arg(&restored)
+        .assert()
+        .success();
+
+    assert_eq!(fs::read(&restored).unwrap(), payload);
+}
+
+#[test]
+fn test_encode_compressed_file_then_decode_to_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+    let secret = dir.path().join("secret.bin");
+    let restored = dir.path().join("restored.bin");
+    let payload = [0u8, 1, 2, 3].repeat(1000);
+    fs::write(&secret, &payload).unwrap();
+
+    pngme()
+        .arg("encode")
+        .arg(&path)
+        .args(["ruSt", "--compress", "--file"])
+        .arg(&secret)
+        .assert()
+        .success();
+    pngme()
+        .arg("decode")
+        .arg(&path)
+        .arg("ruSt")
+        .arg("--out")
+        .arg(&restored)
+        .assert()
+        .success();
+    assert_eq!(fs::read(&restored).unwrap(), payload);
+
+    pngme()
+        .arg("decode")
+        .arg(&path)
+        .args(["ruSt", "--raw", "--out"])
+        .arg(&restored)
+        .assert()
+        .success();
+    let raw = fs::read(&restored).unwrap();
+    assert!(raw.starts_with(b"\0zlib\0"));
+    assert_ne!(raw, payload);
+}
+
+#[test]
+fn test_encode_rejects_message_and_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+    let secret = dir.path().join("secret.bin");
+    fs::write(&secret, b"binary").unwrap();
+
+    pngme()
+        .arg("encode")
+        .arg(&path)
+        .args(["ruSt", "This is a secret", "--file"])
+        .arg(&secret)
+        .assert()
+        .failure();
+
+    assert_eq!(fs::read(&path).unwrap(), BASIC_PNG);
+}
+
+#[test]
+fn test_encode_critical_type_needs_force() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+
+    let assert = pngme()
+        .arg("encode")
+        .arg(&path)
+        .args(["RUST", "This is a secret"])
+        .assert()
+        .failure();
+    let stderr = String::from_utf8(assert.get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("--force"));
+    assert_eq!(fs::read(&path).unwrap(), BASIC_PNG);
+
+    pngme()
+        .arg("encode")
+        .arg(&path)
+        .args(["RUST", "This is a secret", "--force"])
+        .assert()
+        .success();
+    encode(&path, "ruSt", "This is a secret");
+}
+
+#[test]
+fn test_sanitize_removes_private_and_unsafe_chunks() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+    encode(&path, "ruSt", "private and unsafe to copy");
+    encode(&path, "tIME", "public but unsafe to copy");
+    encode(&path, "tEXt", "public and safe to copy");
+
+    let output = stdout_of(pngme().arg("sanitize").arg(&path));
+
+    assert_eq!(output.trim(), "Removed 2 chunks");
+    let png = Png::try_from(fs::read(&path).unwrap().as_slice()).unwrap();
+    let types: Vec<String> = png.chunks().iter().map(|c| c.chunk_type().to_string()).collect();
+    assert_eq!(types, ["IHDR", "IDAT", "tEXt", "IEND"]);
+}
+
+#[test]
+fn test_encode_at_index() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+
+    pngme()
+        .arg("encode")
+        .arg(&path)
+        .args(["ruSt", "This is a secret", "--at", "1"])
+        .assert()
+        .success();
+
+    let png = Png::try_from(fs::read(&path).unwrap().as_slice()).unwrap();
+    let types: Vec<String> = png.chunks().iter().map(|c| c.chunk_type().to_string()).collect();
+    assert_eq!(types, ["IHDR", "ruSt", "IDAT", "IEND"]);
+}
+
+#[test]
+fn test_encode_at_invalid_index() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+
+    pngme()
+        .arg("encode")
+        .arg(&path)
+        .args(["ruSt", "This is a secret", "--at", "0"])
+        .assert()
+        .failure();
+
+    assert_eq!(fs::read(&path).unwrap(), BASIC_PNG);
+}
+
+#[test]
+fn test_print_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+    encode(&path, "ruSt", "This is a secret");
+
+    let output = stdout_of(pngme().arg("print").arg(&path).args(["--format", "json"]));
+    let chunks: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let chunks = chunks.as_array().unwrap();
+
+    assert_eq!(chunks.len(), 4);
+    assert_eq!(chunks[0]["type"], "IHDR");
+    assert_eq!(chunks[0]["length"], 13);
+    assert_eq!(chunks[0]["offset"], 8);
+    assert_eq!(chunks[1]["offset"], 8 + 12 + 13);
+    assert_eq!(chunks[2]["type"], "ruSt");
+    assert_eq!(chunks[2]["crc"].as_str().unwrap().len(), 8);
+}
+
+#[test]
+fn test_print_data_dump() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+    encode(&path, "ruSt", "twenty bytes of text");
+
+    let output = stdout_of(
+        pngme()
+            .arg("print")
+            .arg(&path)
+            .args(["--type", "ruSt", "--data"]),
+    );
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(lines.len(), 3);
+    assert!(lines[0].contains("type: ruSt, length: 20"));
+    assert_eq!(
+        lines[1],
+        "00000000  74 77 65 6e 74 79 20 62  79 74 65 73 20 6f 66 20  |twenty bytes of |"
+    );
+    assert_eq!(
+        lines[2],
+        "00000010  74 65 78 74                                       |text|"
+    );
+}
+
+#[test]
+fn test_extract_binary_data() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+    let payload: Vec<u8> = (0..=255).collect();
+    let mut png = pngme::png::Png::try_from(BASIC_PNG).unwrap();
+    png.append_chunk(pngme::chunk::Chunk::new("ruSt".parse().unwrap(), payload.clone()));
+    fs::write(&path, png.as_bytes()).unwrap();
+    let output = dir.path().join("payload.bin");
+
+    pngme().arg("extract").arg(&path).arg("ruSt").arg(&output).assert().success();
+
+    assert_eq!(fs::read(&output).unwrap(), payload);
+}
+
+#[test]
+fn test_extract_missing_chunk() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+    let output = dir.path().join("payload.bin");
+
+    pngme().arg("extract").arg(&path).arg("ruSt").arg(&output).assert().failure();
+
+    assert!(!output.exists());
+}
+
+#[test]
+fn test_list_counts_chunk_types() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("split.png");
+    fs::write(&path, include_bytes!("fixtures/split_idat.png")).unwrap();
+
+    let output = stdout_of(pngme().arg("list").arg(&path));
+
+    assert_eq!(output, "IDAT 3\nIEND 1\nIHDR 1\n");
+}
+
+#[test]
+fn test_encode_message_from_stdin() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+
+    pngme()
+        .arg("encode")
+        .arg(&path)
+        .args(["ruSt", "-"])
+        .write_stdin("secret from a pipe\n")
+        .assert()
+        .success();
+    let decoded = stdout_of(pngme().arg("decode").arg(&path).arg("ruSt"));
+
+    assert_eq!(decoded, "secret from a pipe\n");
+}
+
+#[test]
+fn test_encode_chunk_type_from_stdin() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+
+    pngme()
+        .arg("encode")
+        .arg(&path)
+        .args(["-", "This is a secret"])
+        .write_stdin("ruSt\n")
+        .assert()
+        .success();
+    let decoded = stdout_of(pngme().arg("decode").arg(&path).arg("ruSt"));
+    assert_eq!(decoded.trim_end(), "This is a secret");
+
+    let assert = pngme()
+        .arg("encode")
+        .arg(&path)
+        .args(["-", "This is a secret"])
+        .write_stdin("rust chunk\n")
+        .assert()
+        .failure();
+    let stderr = String::from_utf8(assert.get_output().stderr.clone()).unwrap();
+    assert!(stderr.contains("chunk type must be exactly 4 characters, got 10"));
+}
+
+#[test]
+fn test_verify_clean_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+
+    assert_eq!(stdout_of(pngme().arg("verify").arg(&path)), "OK\n");
+}
+
+#[test]
+fn test_verify_corrupt_data() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("corrupt.png");
+    let mut bytes = BASIC_PNG.to_vec();
+    // Flip a byte inside the IDAT data, which starts after the signature,
+    // the 25-byte IHDR chunk, and IDAT's own 8-byte header.
+    bytes[8 + 25 + 8 + 4] ^= 0xFF;
+    fs::write(&path, bytes).unwrap();
+
+    let assert = pngme().arg("verify").arg(&path).assert().failure();
+    let stderr = String::from_utf8(assert.get_output().stderr.clone()).unwrap();
+
+    assert!(stderr.contains("CRC mismatch in chunk 1"));
+}
+
+#[test]
+fn test_verify_bad_structure() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("no_iend.png");
+    fs::write(&path, &BASIC_PNG[..BASIC_PNG.len() - 12]).unwrap();
+
+    let assert = pngme().arg("verify").arg(&path).assert().failure();
+    let stderr = String::from_utf8(assert.get_output().stderr.clone()).unwrap();
+
+    assert!(stderr.contains("IEND"));
+}
+
+#[test]
+fn test_diff_after_encode() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = scratch_png(&dir);
+    let output = dir.path().join("out.png");
+    pngme()
+        .arg("encode")
+        .arg(&path)
+        .args(["ruSt", "This is a secret", "--output"])
+        .arg(&output)
+        .assert()
+        .success();
+
+    assert_eq!(stdout_of(pngme().arg("diff").arg(&path).arg(&output)), "+ 2 ruSt\n");
+    assert_eq!(stdout_of(pngme().arg("diff").arg(&path).arg(&path)), "");
+}
+
+#[test]
+fn test_encode_preserves_unknown_chunks() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("unknown.png");
+    // Splice an unknown chunk between IHDR and IDAT, built by hand so it
+    // doesn't depend on the library's own serialization.
+    let data = b"\x00\xffopaque";
+    let mut unknown = (data.len() as u32).to_be_bytes().to_vec();
+    unknown.extend_from_slice(b"xYZw");
+    unknown.extend_from_slice(data);
+    let crc = pngme::chunk::Chunk::calculate_crc(&"xYZw".parse().unwrap(), data);
+    unknown.extend_from_slice(&crc.to_be_bytes());
+    let ihdr_end = 8 + 25;
+    let original = [&BASIC_PNG[..ihdr_end], &unknown, &BASIC_PNG[ihdr_end..]].concat();
+    fs::write(&path, &original).unwrap();
+
+    encode(&path, "ruSt", "This is a secret");
+
+    let encoded = fs::read(&path).unwrap();
+    let iend = &BASIC_PNG[BASIC_PNG.len() - 12..];
+    let new_chunk_start = original.len() - 12;
+    // Everything before IEND is untouched, the new chunk follows, then IEND.
+    assert_eq!(encoded[..new_chunk_start], original[..new_chunk_start]);
+    assert_eq!(&encoded[new_chunk_start + 4..new_chunk_start + 8], b"ruSt");
+    assert_eq!(&encoded[encoded.len() - 12..], iend);
+    assert_eq!(encoded[ihdr_end..ihdr_end + unknown.len()], unknown);
+}
@@ -0,0 +1,21 @@
+use std::process::Command;
+
+/// Builds the library without the `std` feature, so anything that reaches
+/// for `std` outside a `#[cfg(feature = "std")]` gate fails here rather than
+/// on an embedded target.
+#[test]
+fn builds_without_default_features() {
+    let target_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/target/no-std");
+    let output = Command::new(env!("CARGO"))
+        .args(["build", "--lib", "--no-default-features", "--quiet"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env("CARGO_TARGET_DIR", target_dir)
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "no_std build failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,43 @@
+use pngme::chunk::Chunk;
+use pngme::chunk_type::ChunkType;
+use pngme::png::Png;
+use proptest::prelude::*;
+
+fn chunk_type() -> impl Strategy<Value = ChunkType> {
+    let letter = prop_oneof![b'A'..=b'Z', b'a'..=b'z'];
+    [letter.clone(), letter.clone(), letter.clone(), letter]
+        .prop_map(|bytes| ChunkType::try_from(bytes).unwrap())
+}
+
+// Small vectors of plain bytes shrink toward short, all-zero data.
+fn chunk() -> impl Strategy<Value = Chunk> {
+    (chunk_type(), prop::collection::vec(any::<u8>(), 0..256))
+        .prop_map(|(chunk_type, data)| Chunk::new(chunk_type, data))
+}
+
+proptest! {
+    #[test]
+    fn png_round_trips(chunks in prop::collection::vec(chunk(), 0..16)) {
+        let png = Png::from_chunks(chunks);
+        let bytes = png.as_bytes();
+
+        prop_assert_eq!(bytes.len(), png.total_byte_size());
+        prop_assert_eq!(Png::try_from(bytes.as_slice()).unwrap(), png);
+    }
+
+    #[test]
+    fn chunk_round_trips(chunk in chunk()) {
+        prop_assert_eq!(Chunk::try_from(chunk.as_bytes().as_slice()).unwrap(), chunk);
+    }
+
+    #[test]
+    fn arbitrary_bytes_never_panic(bytes in prop::collection::vec(any::<u8>(), 0..512)) {
+        let _ = Png::try_from(bytes.as_slice());
+        let _ = Chunk::try_from(bytes.as_slice());
+
+        let mut with_header = Png::STANDARD_HEADER.to_vec();
+        with_header.extend_from_slice(&bytes);
+        let _ = Png::try_from(with_header.as_slice());
+        let _ = Png::from_bytes_lenient(&with_header);
+    }
+}